@@ -0,0 +1,63 @@
+//! # Bulk rendering benchmark
+//!
+//! Renders one precompiled `Template` for a growing number of `InstanceObject`s and reports the time
+//! spent per instance, along with the ratio between the per-instance cost of the largest and of the smallest run.
+//! The per-instance cost should stay flat for rendering to scale linearly; timings depend on the machine,
+//! so the ratio is reported rather than asserted.
+//!
+//! Run with `cargo bench --bench bulk_render`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use hound_cast::core::{
+    instance::instance_object::{InstanceObject, InstanceObjectBuilder},
+    meta::{meta_entity::MetaAttributes, meta_object::MetaObject},
+    template::template_builder::TemplateBuilder,
+};
+
+const SIZES: [usize; 4] = [1_000, 5_000, 20_000, 50_000];
+
+fn instances(meta_object: &MetaObject, count: usize) -> Vec<InstanceObject> {
+    (0..count)
+        .map(|index| {
+            let mut builder = InstanceObjectBuilder::new(meta_object, &format!("Race{index}"));
+            builder.update_entity("name", Some(&format!("Race number {index}"))).unwrap();
+            builder.update_entity("prize", Some(&(index % 30_000).to_string())).unwrap();
+            builder.update_entity("city", if index % 2 == 0 { Some("Paris") } else { None }).unwrap();
+            builder.build().unwrap()
+        })
+        .collect()
+}
+
+fn main() {
+    let mut meta_object = MetaObject::new_meta("Race").unwrap();
+    meta_object.update_entity("name", MetaAttributes::Text);
+    meta_object.update_entity("prize", MetaAttributes::I16);
+    meta_object.update_entity("city", MetaAttributes::Text);
+
+    let content = "<h1>[@name|upper]</h1>\n[#if @city]<p>In [@city]</p>[#else]<p>Online</p>[#endif]\n<p>Prize: [@prize|number]</p>";
+    let template = TemplateBuilder::instanciate(content, &meta_object).build().unwrap();
+
+    let mut per_instance = Vec::new();
+    for count in SIZES {
+        let objects = instances(&meta_object, count);
+
+        let start = Instant::now();
+        for object in &objects {
+            black_box(template.build_from_instance(black_box(object)).unwrap());
+        }
+        let elapsed = start.elapsed();
+
+        let cost = elapsed.as_secs_f64() / count as f64;
+        per_instance.push(cost);
+        println!(
+            "{count:>7} instances: {:>10.3?} total, {:>8.3?} per instance",
+            elapsed,
+            Duration::from_secs_f64(cost)
+        );
+    }
+
+    let ratio = per_instance[per_instance.len() - 1] / per_instance[0];
+    println!("per-instance cost ratio (largest / smallest): {ratio:.2}");
+}
@@ -1,5 +1,6 @@
 use hound_cast::{core::{instance::instance_object::InstanceObjectBuilder, meta::{meta_entity::MetaAttributes, meta_object::MetaObject}, model::UniqueId, template::template_builder::TemplateBuilder}, storage::in_memory::object_collections::{InstanceObjectCollection, MetaObjectCollection}};
 use hound_cast::core::model::unique_id::Identifier;
+use std::collections::HashMap;
 
 
 fn main() {
@@ -11,14 +12,20 @@ fn main() {
     let mut instance_object_collection = InstanceObjectCollection::new();
     
     let mut marathon = MetaObject::new_meta("Marathon").unwrap();
-    marathon.update_entity("prize", MetaAttributes::I16);
-    marathon.update_entity("ref_link", MetaAttributes::Text);
+    marathon.update_entity("prize", MetaAttributes::I32);
+    marathon.update_entity("ref_link", MetaAttributes::Url);
    
     meta_object_collection.insert(&marathon);
 
     let mut marathon_paris_build = InstanceObjectBuilder::new(&marathon, "Paris Marathon");
-    marathon_paris_build.update_entity("prize", Some("2030")).unwrap_or_else(|err|println!("{}",err));
-    marathon_paris_build.update_entity("ref_link", Some("link to ref")).unwrap_or_else(|err|println!("{}",err));
+    let inputs = HashMap::from([
+        ("prize".to_string(), "45000".to_string()),
+        ("ref_link".to_string(), "https://www.schneiderelectricparismarathon.com".to_string()),
+    ]);
+    if let Err(report) = marathon_paris_build.update_entities(&inputs) {
+        eprintln!("{}", report);
+        return;
+    }
     marathon_paris_build.populate_missing_meta_entites();
     let marathon_paris = marathon_paris_build.build().unwrap();
     
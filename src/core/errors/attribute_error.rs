@@ -2,16 +2,110 @@
 //!
 //! The `AttributeError` enum provides descriptive error variants for issues like type mismatches or invalid input.
 
-use std::fmt;
-
 use thiserror::Error;
 
+use super::ExpressionError;
+
 #[derive(Debug, PartialEq,Error)]
 /// Enum representing errors that can occur during attribute parsing and validation.
 pub enum AttributeError{
     #[error("AttributeError: Invalid Type {0}")]
     InvalidType(String),
     #[error("AttributeError: None matching type {0}")]
-    NonMatchingType(String)
+    NonMatchingType(String),
+    /// Raised when a decimal amount has more fractional digits than its meta definition allows.
+    #[error("AttributeError: Expect at most {expected} decimal places got {found}")]
+    ScaleMismatch {
+        expected: u32,
+        found: u32,
+    },
+    /// Raised when a money amount is written in another currency than its meta definition.
+    #[error("AttributeError: Expect currency {expected} got {found}")]
+    CurrencyMismatch {
+        expected: String,
+        found: String,
+    },
+    /// Raised when a currency code is not a supported ISO-4217 code.
+    #[error("AttributeError: Unknown currency {0}")]
+    UnknownCurrency(String),
+    /// Raised when a value is not one of the keys of an `Enum` attribute.
+    #[error("AttributeError: Value {value} is not one of {allowed:?}")]
+    NotAllowed {
+        value: String,
+        allowed: Vec<String>,
+    },
+    /// Raised when a value is not an absolute `http` or `https` URL.
+    #[error("AttributeError: Invalid URL {0}")]
+    InvalidUrl(String),
+    /// Raised when a value is not an email address.
+    #[error("AttributeError: Invalid email address {0}")]
+    InvalidEmail(String),
+    /// Raised when a value is not a phone number in the E.164 format.
+    #[error("AttributeError: Invalid E.164 phone number {0}")]
+    InvalidPhone(String),
+    /// Raised when a value is not made of lowercase letters and digits separated by single dashes.
+    #[error("AttributeError: Invalid slug {0}")]
+    InvalidSlug(String),
+    /// Raised when a nested object refers to a `MetaObject` the builder was not given.
+    #[error("AttributeError: Unknown Meta Object {0}")]
+    UnknownMetaObject(String),
+    /// Raised when an entity, given by name or dotted path, is not declared by the `MetaObject`.
+    #[error("AttributeError: Unknown entity {0}")]
+    UnknownEntity(String),
+    /// Raised when a `MetaObject` embeds itself through `Object` entities, directly or through other `MetaObject`s.
+    #[error("AttributeError: Meta Object {0} embeds itself")]
+    CyclicObject(String),
+    /// Raised when an embedded `MetaObject` has computed entities, which are only computed on the embedding object.
+    #[error("AttributeError: Computed entity {0} cannot be embedded in another object")]
+    EmbeddedComputedEntity(String),
+    /// Raised when a reference points to an instance that is unknown or not an instance of the referenced `MetaObject`.
+    #[error("AttributeError: Instance {id} is not a known instance of Meta Object {meta}")]
+    InvalidReference {
+        id: String,
+        meta: String,
+    },
+    /// Raised when the value of an entity breaks one of the constraints declared on its `MetaEntity`.
+    #[error("AttributeError: Entity {entity} breaks rule {rule} with value {value}")]
+    ConstraintViolation {
+        entity: String,
+        rule: String,
+        value: String,
+    },
+    /// Raised when a constraint is declared on an entity whose type it does not apply to, e.g. `min` on `Text`.
+    #[error("AttributeError: Rule {rule} does not apply to entity {entity}")]
+    InvalidConstraint {
+        entity: String,
+        rule: String,
+    },
+    /// Raised when the pattern of a constraint is not a valid regular expression.
+    #[error("AttributeError: Invalid pattern {0}")]
+    InvalidPattern(String),
+    /// Raised when a value is given to a computed entity, whose value only comes from its expression.
+    #[error("AttributeError: Entity {0} is computed and cannot be updated")]
+    ReadOnlyEntity(String),
+    /// Raised when the expression of a computed entity fails to evaluate, e.g. on a division by zero.
+    #[error("AttributeError: Entity {entity} cannot be computed: {error}")]
+    ComputationFailed {
+        entity: String,
+        error: ExpressionError,
+    },
+    /// Raised when the values of an object break one of its rules, the entities the rule reads being listed.
+    #[error("AttributeError: Rule {rule} is broken by entities {entities:?}")]
+    RuleViolation {
+        rule: String,
+        entities: Vec<String>,
+    },
+    /// Raised when a rule of an object fails to evaluate, e.g. on a division by zero.
+    #[error("AttributeError: Rule {rule} cannot be evaluated: {error}")]
+    InvalidRule {
+        rule: String,
+        error: ExpressionError,
+    },
+    /// Raised when an element of a list or set input cannot be parsed.
+    #[error("AttributeError: Invalid element {index}: {error}")]
+    InvalidElement {
+        index: usize,
+        error: Box<AttributeError>,
+    },
 }
 
@@ -0,0 +1,55 @@
+//! This module defines errors that may occur when computed entities are defined or evaluated.
+//!
+//! The `ExpressionError` enum covers syntax errors, type errors found when an expression is checked against
+//! its `MetaObject`, rules that do not compute a boolean, dependency cycles between computed entities and
+//! failures at evaluation time.
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+/// Enum representing errors that can occur while parsing, checking or evaluating an expression.
+pub enum ExpressionError {
+    /// Raised when the expression is malformed, the position being a byte offset in its source.
+    #[error("ExpressionError: Invalid syntax at {position}: {message}")]
+    InvalidSyntax {
+        position: usize,
+        message: String,
+    },
+    /// Raised when the expression references an entity the `MetaObject` does not define.
+    #[error("ExpressionError: Unknown entity {0}")]
+    UnknownEntity(String),
+    /// Raised when the expression references an entity whose type expressions cannot read, e.g. a `List`.
+    #[error("ExpressionError: Entity {entity} of type {attribute} cannot be used in expressions")]
+    UnsupportedEntity {
+        entity: String,
+        attribute: String,
+    },
+    /// Raised when an operator is applied to operands of types it does not accept, e.g. `text * integer`.
+    #[error("ExpressionError: Operator {operator} does not apply to {operands:?}")]
+    InvalidOperands {
+        operator: String,
+        operands: Vec<String>,
+    },
+    /// Raised when the expression computes a value the computed entity cannot hold.
+    #[error("ExpressionError: Entity {entity} of type {expected} cannot hold a {found} value")]
+    IncompatibleResult {
+        entity: String,
+        expected: String,
+        found: String,
+    },
+    /// Raised when the expression of a rule does not compute a boolean.
+    #[error("ExpressionError: Rule {rule} must compute a bool, not a {found} value")]
+    InvalidRule {
+        rule: String,
+        found: String,
+    },
+    /// Raised when computed entities depend on each other, the entities being listed along the cycle.
+    #[error("ExpressionError: Cyclic computation {0:?}")]
+    CyclicComputation(Vec<String>),
+    /// Raised at evaluation time when a number is divided by zero.
+    #[error("ExpressionError: Division by zero")]
+    DivisionByZero,
+    /// Raised at evaluation time when a computation exceeds the range of its type.
+    #[error("ExpressionError: Overflow")]
+    Overflow,
+}
@@ -5,12 +5,14 @@
 
 
 pub mod attribute_error;
+pub mod expression_error;
 pub mod object_collection_error;
 pub mod object_error;
 pub mod template_error;
 pub mod unique_id_errors;
 
 pub use attribute_error::AttributeError;
+pub use expression_error::ExpressionError;
 pub use object_collection_error::ObjectCollectionError;
 pub use object_error::ObjectError;
 pub use template_error::TemplateError;
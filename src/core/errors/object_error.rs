@@ -1,8 +1,19 @@
 use thiserror::Error;
 
+use super::{AttributeError, UniqueIdError};
+
 
 #[derive(Debug,Error)]
 pub enum ObjectError{
     #[error("ObjectError: Meta Object is Missing")]
-    MissingMetaObject
+    MissingMetaObject,
+    /// Raised when required entities, listed by name or dotted path, are not given a value.
+    #[error("ObjectError: Missing required entities {0:?}")]
+    MissingRequiredEntities(Vec<String>),
+    /// Raised when the id of the object cannot be generated.
+    #[error("ObjectError: {0}")]
+    InvalidId(#[from] UniqueIdError),
+    /// Raised when the value of an entity is rejected, e.g. when it breaks a constraint of its `MetaEntity`.
+    #[error("ObjectError: {0}")]
+    InvalidEntity(#[from] AttributeError),
 }
\ No newline at end of file
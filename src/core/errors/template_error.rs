@@ -23,6 +23,99 @@ pub enum TemplateError {
     #[error("TemplateError: Following entities {0:?} are missing from the content being built")]
     MissingEntitiesFromMetaObject(Vec<String>),
 
+    /// Raised when the template references entities, in placeholders or conditions, unknown to the `MetaObject`.
+    #[error("TemplateError: Following entities {0:?} are referenced by the content but missing from the Meta Object")]
+    UnknownEntitiesInContent(Vec<String>),
+
+    /// Raised when a filter does not accept the type of the value it receives.
+    #[error("TemplateError: Filter {filter} cannot be applied to entity {entity} of type {attribute}")]
+    InvalidFilter {
+        entity: String,
+        filter: String,
+        attribute: String,
+    },
+
+    /// Raised when a filter cannot process a value while rendering.
+    #[error("TemplateError: Filter {filter} failed on value {value}")]
+    FilterFailed {
+        filter: String,
+        value: String,
+    },
+
+    /// Raised when placeholder values are missing and the `NullPolicy` does not allow it.
+    #[error("TemplateError: Following entities {0:?} have no value")]
+    NullEntities(Vec<String>),
+
+    /// Raised when an included partial cannot be found in the `TemplateCollection`.
+    #[error("TemplateError: Partial {0} is missing from the template collection")]
+    MissingPartial(String),
+
+    /// Raised when an included partial references entities unknown to the including template's `MetaObject`.
+    #[error("TemplateError: Partial {partial} is not compatible with Meta Object {meta}")]
+    IncompatiblePartial {
+        partial: String,
+        meta: String,
+    },
+
+    /// Raised when partials include each other, listing the include keys leading to the cycle.
+    #[error("TemplateError: Partials {0:?} include each other")]
+    CyclicPartial(Vec<String>),
+
+    /// Raised when a template extends a layout bound to another `MetaObject`.
+    #[error("TemplateError: Layout {layout} is not bound to Meta Object {meta}")]
+    IncompatibleLayout {
+        layout: String,
+        meta: String,
+    },
+
+    /// Raised when a template overrides blocks its layout chain does not define.
+    #[error("TemplateError: Following blocks {0:?} are not defined by the layout")]
+    UnknownBlocks(Vec<String>),
+
+    /// Raised when the rendered output cannot be written to its sink.
+    #[error("TemplateError: Failed to write the rendered output: {0}")]
+    IoError(#[from] WriteError),
+
+    /// Raised when a loop iterates over an entity that is not a collection.
+    #[error("TemplateError: Cannot loop over entity {entity} of type {attribute}")]
+    InvalidLoop {
+        entity: String,
+        attribute: String,
+    },
+
+    /// Raised when the template content cannot be parsed.
+    #[error("TemplateError: Invalid syntax at byte {position}: {reason}")]
+    InvalidSyntax {
+        position: usize,
+        reason: String,
+    },
+
     #[error("TemplateError: Propagated error: {0}")]
     PropagatedError(String)
-}
\ No newline at end of file
+}
+
+/// The `std::io::Error` raised by the sink of a rendered output, kept as the source of `TemplateError::IoError`.
+///
+/// Two errors are equal when they have the same kind and message, `std::io::Error` not being comparable.
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct WriteError(#[from] std::io::Error);
+
+impl WriteError {
+    /// Returns the error raised by the sink.
+    pub fn get_error(&self) -> &std::io::Error {
+        &self.0
+    }
+}
+
+impl PartialEq for WriteError {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind() == other.0.kind() && self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl From<std::io::Error> for TemplateError {
+    fn from(error: std::io::Error) -> Self {
+        TemplateError::IoError(WriteError::from(error))
+    }
+}
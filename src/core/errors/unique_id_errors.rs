@@ -12,4 +12,11 @@ pub enum UniqueIdError {
     /// Error indicating an invalid UniqueId format.
     #[error("UniqueId: Invalid UniqueId format. Expected 3 parts but got {0}")]
     WrongFormat(usize),
+
+    /// Error indicating a part of a UniqueId that could not have been generated, such as a non-numeric timestamp.
+    #[error("UniqueId: Invalid {part} {value}")]
+    InvalidPart {
+        part: String,
+        value: String,
+    },
 }
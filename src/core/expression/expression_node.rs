@@ -0,0 +1,363 @@
+//! # Expression
+//!
+//! An `Expression` is the tree parsed from the source of a computed entity, e.g. `prize / distance`.
+//!
+//! ## Responsibilities:
+//! - Lists the entities the expression reads, so that computed entities are evaluated after their inputs.
+//! - Infers the `ValueType` of the expression from the `MetaAttributes` of the entities it reads.
+//! - Evaluates the expression against the values of those entities.
+//!
+//! ## Operators:
+//! - `+`, `-`, `*`: integers give integers, decimals or an integer and a decimal give decimals, and any float
+//!   gives a float.
+//! - `/`: always gives a float, dividing by zero failing with `ExpressionError::DivisionByZero`.
+//! - `+` with a text operand concatenates both operands written as text, e.g. `name + " (" + year + ")"`.
+//! - `==` and `!=` compare values of the same type, numbers of any type being compared with each other.
+//! - `<`, `<=`, `>` and `>=` order numbers, texts, dates, date-times and times, e.g. `end_date > start_date`.
+//! - `&&`, `||` and `!` combine booleans, `&&` and `||` only evaluating their right operand when needed.
+//! - `is_set(entity)` tells whether an entity has a value.
+//! - A missing operand makes the whole operation missing, except for `&&` and `||` when the other operand
+//!   decides alone: `false && missing` is `false`, `true || missing` is `true`.
+
+use std::collections::BTreeSet;
+
+use crate::core::{errors::ExpressionError, model::Decimal};
+
+use super::expression_value::{Value, ValueType};
+
+/// A binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    And,
+    Or,
+}
+
+impl Operator {
+    /// Returns the operator as written in expressions.
+    pub fn get_symbol(&self) -> &str {
+        match &self {
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::Less => "<",
+            Operator::LessOrEqual => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterOrEqual => ">=",
+            Operator::And => "&&",
+            Operator::Or => "||",
+        }
+    }
+}
+
+/// A node of a parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// A number, text or boolean written in the expression.
+    Literal(Value),
+    /// The value of an entity of the same object.
+    Entity(String),
+    /// The opposite of a number.
+    Negate(Box<Expression>),
+    /// The negation of a boolean.
+    Not(Box<Expression>),
+    /// Whether an entity of the same object has a value.
+    IsSet(String),
+    /// An operator applied to two operands.
+    Binary {
+        operator: Operator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+}
+
+impl Expression {
+    /// Returns the names of the entities read by the expression, sorted.
+    pub fn get_references(&self) -> BTreeSet<&str> {
+        match &self {
+            Expression::Literal(_) => BTreeSet::new(),
+            Expression::Entity(name) | Expression::IsSet(name) => BTreeSet::from([name.as_str()]),
+            Expression::Negate(operand) | Expression::Not(operand) => operand.get_references(),
+            Expression::Binary { left, right, .. } => {
+                let mut references = left.get_references();
+                references.extend(right.get_references());
+                references
+            }
+        }
+    }
+
+    /// Returns the type of the values the expression computes, given the types of the entities it reads.
+    pub fn infer_type(
+        &self,
+        entity_type: &dyn Fn(&str) -> Result<ValueType, ExpressionError>,
+    ) -> Result<ValueType, ExpressionError> {
+        match &self {
+            Expression::Literal(value) => value.get_type().ok_or_else(|| ExpressionError::InvalidOperands {
+                operator: "literal".to_string(),
+                operands: vec![value.to_string()],
+            }),
+            Expression::Entity(name) => entity_type(name),
+            Expression::Negate(operand) => match operand.infer_type(entity_type)? {
+                operand if operand.is_numeric() => Ok(operand),
+                operand => Err(ExpressionError::InvalidOperands {
+                    operator: "-".to_string(),
+                    operands: vec![operand.to_string()],
+                }),
+            },
+            Expression::Not(operand) => match operand.infer_type(entity_type)? {
+                ValueType::Bool => Ok(ValueType::Bool),
+                operand => Err(ExpressionError::InvalidOperands {
+                    operator: "!".to_string(),
+                    operands: vec![operand.to_string()],
+                }),
+            },
+            Expression::IsSet(name) => entity_type(name).map(|_| ValueType::Bool),
+            Expression::Binary { operator, left, right } => {
+                let (left, right) = (left.infer_type(entity_type)?, right.infer_type(entity_type)?);
+                Self::binary_type(*operator, left, right).ok_or_else(|| ExpressionError::InvalidOperands {
+                    operator: operator.get_symbol().to_string(),
+                    operands: vec![left.to_string(), right.to_string()],
+                })
+            }
+        }
+    }
+
+    /// Evaluates the expression, reading the values of entities with `entity_value`.
+    pub fn evaluate(&self, entity_value: &dyn Fn(&str) -> Value) -> Result<Value, ExpressionError> {
+        match &self {
+            Expression::Literal(value) => Ok(value.clone()),
+            Expression::Entity(name) => Ok(entity_value(name)),
+            Expression::Negate(operand) => match operand.evaluate(entity_value)? {
+                Value::Null => Ok(Value::Null),
+                Value::Integer(integer) => integer.checked_neg().map(Value::Integer).ok_or(ExpressionError::Overflow),
+                Value::Float(number) => Ok(Value::Float(-number)),
+                Value::Decimal(number) => Decimal::new(0, 0)
+                    .checked_sub(&number)
+                    .map(Value::Decimal)
+                    .ok_or(ExpressionError::Overflow),
+                operand => Err(ExpressionError::InvalidOperands {
+                    operator: "-".to_string(),
+                    operands: vec![operand.to_string()],
+                }),
+            },
+            Expression::Not(operand) => match operand.evaluate(entity_value)? {
+                Value::Null => Ok(Value::Null),
+                Value::Bool(boolean) => Ok(Value::Bool(!boolean)),
+                operand => Err(ExpressionError::InvalidOperands {
+                    operator: "!".to_string(),
+                    operands: vec![operand.to_string()],
+                }),
+            },
+            Expression::IsSet(name) => Ok(Value::Bool(entity_value(name) != Value::Null)),
+            Expression::Binary { operator: operator @ (Operator::And | Operator::Or), left, right } => {
+                Self::combine(*operator, left, right, entity_value)
+            }
+            Expression::Binary { operator, left, right } => {
+                Self::apply(*operator, left.evaluate(entity_value)?, right.evaluate(entity_value)?)
+            }
+        }
+    }
+
+    /// Returns the type an operator gives for the types of its operands, `None` if it does not apply to them.
+    fn binary_type(operator: Operator, left: ValueType, right: ValueType) -> Option<ValueType> {
+        let numeric = left.is_numeric() && right.is_numeric();
+        match (operator, left, right) {
+            (Operator::Equal | Operator::NotEqual, _, _) => (numeric || left == right).then_some(ValueType::Bool),
+            (Operator::Less | Operator::LessOrEqual | Operator::Greater | Operator::GreaterOrEqual, _, _) => {
+                (numeric || (left == right && left != ValueType::Bool)).then_some(ValueType::Bool)
+            }
+            (Operator::And | Operator::Or, ValueType::Bool, ValueType::Bool) => Some(ValueType::Bool),
+            (Operator::And | Operator::Or, _, _) => None,
+            (Operator::Add, ValueType::Text, _) | (Operator::Add, _, ValueType::Text) => Some(ValueType::Text),
+            _ if !numeric => None,
+            (Operator::Divide, _, _) => Some(ValueType::Float),
+            (_, ValueType::Float, _) | (_, _, ValueType::Float) => Some(ValueType::Float),
+            (_, ValueType::Decimal, _) | (_, _, ValueType::Decimal) => Some(ValueType::Decimal),
+            _ => Some(ValueType::Integer),
+        }
+    }
+
+    /// Evaluates `&&` or `||`, the right operand only when the left one does not decide alone.
+    fn combine(
+        operator: Operator,
+        left: &Expression,
+        right: &Expression,
+        entity_value: &dyn Fn(&str) -> Value,
+    ) -> Result<Value, ExpressionError> {
+        let decisive = operator == Operator::Or;
+        let left = left.evaluate(entity_value)?;
+        if left == Value::Bool(decisive) {
+            return Ok(left);
+        }
+        match (left, right.evaluate(entity_value)?) {
+            (_, Value::Bool(right)) if right == decisive => Ok(Value::Bool(decisive)),
+            (Value::Bool(_), Value::Bool(_)) => Ok(Value::Bool(!decisive)),
+            (Value::Null | Value::Bool(_), Value::Null | Value::Bool(_)) => Ok(Value::Null),
+            (left, right) => Err(ExpressionError::InvalidOperands {
+                operator: operator.get_symbol().to_string(),
+                operands: vec![left.to_string(), right.to_string()],
+            }),
+        }
+    }
+
+    fn apply(operator: Operator, left: Value, right: Value) -> Result<Value, ExpressionError> {
+        let invalid = || ExpressionError::InvalidOperands {
+            operator: operator.get_symbol().to_string(),
+            operands: vec![left.to_string(), right.to_string()],
+        };
+
+        match (operator, &left, &right) {
+            (_, Value::Null, _) | (_, _, Value::Null) => Ok(Value::Null),
+            (Operator::Equal | Operator::NotEqual | Operator::Less | Operator::LessOrEqual | Operator::Greater | Operator::GreaterOrEqual, _, _) => {
+                let order = left.compare(&right).ok_or_else(invalid)?;
+                Ok(Value::Bool(match operator {
+                    Operator::Equal => order.is_eq(),
+                    Operator::NotEqual => order.is_ne(),
+                    Operator::Less => order.is_lt(),
+                    Operator::LessOrEqual => order.is_le(),
+                    Operator::Greater => order.is_gt(),
+                    _ => order.is_ge(),
+                }))
+            }
+            (Operator::Add, Value::Text(_), _) | (Operator::Add, _, Value::Text(_)) => Ok(Value::Text(format!("{left}{right}"))),
+            (Operator::Divide, _, _) => match (left.as_f64(), right.as_f64()) {
+                (Some(_), Some(0.0)) => Err(ExpressionError::DivisionByZero),
+                (Some(dividend), Some(divisor)) => Ok(Value::Float(dividend / divisor)),
+                _ => Err(invalid()),
+            },
+            (_, Value::Float(_), _) | (_, _, Value::Float(_)) => match (left.as_f64(), right.as_f64()) {
+                (Some(left), Some(right)) => Ok(Value::Float(match operator {
+                    Operator::Add => left + right,
+                    Operator::Subtract => left - right,
+                    _ => left * right,
+                })),
+                _ => Err(invalid()),
+            },
+            (_, Value::Integer(left), Value::Integer(right)) => match operator {
+                Operator::Add => left.checked_add(*right),
+                Operator::Subtract => left.checked_sub(*right),
+                _ => left.checked_mul(*right),
+            }
+            .map(Value::Integer)
+            .ok_or(ExpressionError::Overflow),
+            _ => match (left.as_decimal(), right.as_decimal()) {
+                (Some(left), Some(right)) => match operator {
+                    Operator::Add => left.checked_add(&right),
+                    Operator::Subtract => left.checked_sub(&right),
+                    _ => left.checked_mul(&right),
+                }
+                .map(Value::Decimal)
+                .ok_or(ExpressionError::Overflow),
+                _ => Err(invalid()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::expression::expression_parser::ExpressionParser;
+
+    fn evaluate(source: &str) -> Result<Value, ExpressionError> {
+        let values = |name: &str| match name {
+            "status" => Value::Text("published".to_string()),
+            "closed" => Value::Bool(false),
+            "name" => Value::Text("Paris Marathon".to_string()),
+            "year" => Value::Integer(2024),
+            "prize" => Value::Integer(45000),
+            "distance" => Value::Float(42.195),
+            "fee" => Value::Decimal(Decimal::new(12050, 2)),
+            _ => Value::Null,
+        };
+        ExpressionParser::parse(source).unwrap().evaluate(&values)
+    }
+
+    fn infer(source: &str) -> Result<ValueType, ExpressionError> {
+        let types = |name: &str| match name {
+            "name" => Ok(ValueType::Text),
+            "year" | "prize" => Ok(ValueType::Integer),
+            "fee" => Ok(ValueType::Decimal),
+            "closed" => Ok(ValueType::Bool),
+            "start_date" => Ok(ValueType::Date),
+            name => Err(ExpressionError::UnknownEntity(name.to_string())),
+        };
+        ExpressionParser::parse(source).unwrap().infer_type(&types)
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic() {
+        assert_eq!(evaluate("name + \" (\" + year + \")\""), Ok(Value::Text("Paris Marathon (2024)".to_string())));
+        assert_eq!(evaluate("prize / 1000"), Ok(Value::Float(45.0)));
+        assert_eq!(evaluate("(prize - 5000) * 2"), Ok(Value::Integer(80000)));
+        assert_eq!(evaluate("fee * 2 + 0.5"), Ok(Value::Decimal(Decimal::new(24150, 2))));
+        assert_eq!(evaluate("-fee"), Ok(Value::Decimal(Decimal::new(-12050, 2))));
+        assert!(matches!(evaluate("prize / distance"), Ok(Value::Float(number)) if (number - 1066.477).abs() < 0.001));
+    }
+
+    #[test]
+    fn test_evaluate_failures() {
+        assert_eq!(evaluate("prize + missing"), Ok(Value::Null));
+        assert_eq!(evaluate("prize / (year - 2024)"), Err(ExpressionError::DivisionByZero));
+        assert_eq!(evaluate("170141183460469231731687303715884105727 + 1"), Err(ExpressionError::Overflow));
+    }
+
+    #[test]
+    fn test_evaluate_logic() {
+        assert_eq!(evaluate("prize >= 45000 && fee < 120.5"), Ok(Value::Bool(false)));
+        assert_eq!(evaluate("prize == 45000.0 && !closed"), Ok(Value::Bool(true)));
+        assert_eq!(evaluate("status != \"published\" || is_set(ref_link)"), Ok(Value::Bool(false)));
+        assert_eq!(evaluate("is_set(distance) && year > 2000"), Ok(Value::Bool(true)));
+        assert_eq!(evaluate("missing > 0"), Ok(Value::Null));
+        assert_eq!(evaluate("missing > 0 || closed"), Ok(Value::Null));
+        assert_eq!(evaluate("missing > 0 && closed"), Ok(Value::Bool(false)));
+        assert_eq!(evaluate("!closed || prize / (year - 2024) > 0"), Ok(Value::Bool(true)));
+    }
+
+    #[test]
+    fn test_infer_type() {
+        assert_eq!(infer("name + year"), Ok(ValueType::Text));
+        assert_eq!(infer("prize * year"), Ok(ValueType::Integer));
+        assert_eq!(infer("prize / year"), Ok(ValueType::Float));
+        assert_eq!(infer("fee + prize"), Ok(ValueType::Decimal));
+        assert_eq!(
+            infer("name * 2"),
+            Err(ExpressionError::InvalidOperands {
+                operator: "*".to_string(),
+                operands: vec!["text".to_string(), "integer".to_string()]
+            })
+        );
+        assert_eq!(
+            infer("-true"),
+            Err(ExpressionError::InvalidOperands {
+                operator: "-".to_string(),
+                operands: vec!["bool".to_string()]
+            })
+        );
+        assert_eq!(infer("prize + distance"), Err(ExpressionError::UnknownEntity("distance".to_string())));
+        assert_eq!(infer("fee < prize && !closed"), Ok(ValueType::Bool));
+        assert_eq!(infer("start_date >= start_date || is_set(name)"), Ok(ValueType::Bool));
+        assert!(infer("start_date > prize").is_err());
+        assert!(infer("closed < true").is_err());
+        assert!(infer("prize && closed").is_err());
+        assert!(infer("!name").is_err());
+    }
+
+    #[test]
+    fn test_get_references() {
+        let expression = ExpressionParser::parse("prize / distance + prize").unwrap();
+        assert_eq!(expression.get_references().into_iter().collect::<Vec<&str>>(), vec!["distance", "prize"]);
+    }
+}
@@ -0,0 +1,384 @@
+//! # ExpressionParser
+//!
+//! The `ExpressionParser` turns the source of a computed entity into an `Expression`.
+//!
+//! ## Responsibilities:
+//! - Splits the source into numbers, texts, names, operators and parentheses, ignoring whitespace.
+//! - Applies the usual precedence, from the loosest to the tightest: `||`, `&&`, comparisons, `+` and `-`,
+//!   `*` and `/`, then the unary `-` and `!`, operators of the same precedence being applied from left to right.
+//! - Rejects malformed sources with the byte position of the first unexpected token.
+//!
+//! ## Literals:
+//! - Numbers without a fractional part are integers, e.g. `1000`, numbers with one are decimals, e.g. `0.5`.
+//! - Texts are written between double quotes, `\"` and `\\` escaping a quote and a backslash.
+//! - `true` and `false` are booleans, any other name referencing an entity of the object.
+//!
+//! ## Functions:
+//! - `is_set(entity)`: whether the entity has a value.
+
+use crate::core::{errors::ExpressionError, model::Decimal};
+
+use super::expression_node::{Expression, Operator};
+use super::expression_value::Value;
+
+/// Operators and punctuation, two-character symbols first so that `<=` is not read as `<`.
+const SYMBOLS: [&str; 15] = ["==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "+", "-", "*", "/", "(", ")"];
+
+/// Flat token produced by scanning the source.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(String),
+    Text(String),
+    Name(String),
+    Symbol(&'static str),
+}
+
+/// Parser producing an `Expression` from its source.
+pub struct ExpressionParser;
+
+/// Tokens of the source, with their positions, and the index of the next one to read.
+struct Cursor {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+    end: usize,
+}
+
+impl ExpressionParser {
+    /// Parses the source of an expression, rejecting malformed sources with `ExpressionError::InvalidSyntax`.
+    pub fn parse(source: &str) -> Result<Expression, ExpressionError> {
+        let mut cursor = Cursor {
+            tokens: Self::tokenize(source)?,
+            next: 0,
+            end: source.len(),
+        };
+
+        let expression = Self::parse_or(&mut cursor)?;
+        match cursor.peek() {
+            Some((token, position)) => Err(Self::syntax_error(position, &format!("unexpected {}", Self::describe(token)))),
+            None => Ok(expression),
+        }
+    }
+
+    /// Scans the source into tokens, each with the byte position it starts at.
+    fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+        let mut tokens = Vec::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((position, c)) = chars.next() {
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '0'..='9' => {
+                    let mut number = c.to_string();
+                    while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+                        number.push(c);
+                    }
+                    Token::Number(number)
+                }
+                '"' => {
+                    let mut text = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '"')) => break,
+                            Some((_, '\\')) => match chars.next() {
+                                Some((_, c @ ('"' | '\\'))) => text.push(c),
+                                Some((escape, _)) => return Err(Self::syntax_error(escape, "invalid escape")),
+                                None => return Err(Self::syntax_error(position, "unterminated text")),
+                            },
+                            Some((_, c)) => text.push(c),
+                            None => return Err(Self::syntax_error(position, "unterminated text")),
+                        }
+                    }
+                    Token::Text(text)
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => {
+                    let mut name = c.to_string();
+                    while let Some((_, c)) = chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '_') {
+                        name.push(c);
+                    }
+                    Token::Name(name)
+                }
+                c => match SYMBOLS.iter().find(|symbol| source[position..].starts_with(*symbol)) {
+                    Some(symbol) => {
+                        for _ in 1..symbol.len() {
+                            chars.next();
+                        }
+                        Token::Symbol(symbol)
+                    }
+                    None => return Err(Self::syntax_error(position, &format!("unexpected character '{c}'"))),
+                },
+            };
+            tokens.push((token, position));
+        }
+
+        Ok(tokens)
+    }
+
+    /// Parses operands separated by `||`.
+    fn parse_or(cursor: &mut Cursor) -> Result<Expression, ExpressionError> {
+        let mut expression = Self::parse_and(cursor)?;
+        while let Some(operator) = cursor.next_operator(&[("||", Operator::Or)]) {
+            expression = Self::binary(operator, expression, Self::parse_and(cursor)?);
+        }
+        Ok(expression)
+    }
+
+    /// Parses operands separated by `&&`.
+    fn parse_and(cursor: &mut Cursor) -> Result<Expression, ExpressionError> {
+        let mut expression = Self::parse_comparison(cursor)?;
+        while let Some(operator) = cursor.next_operator(&[("&&", Operator::And)]) {
+            expression = Self::binary(operator, expression, Self::parse_comparison(cursor)?);
+        }
+        Ok(expression)
+    }
+
+    /// Parses operands separated by `==`, `!=`, `<`, `<=`, `>` or `>=`.
+    fn parse_comparison(cursor: &mut Cursor) -> Result<Expression, ExpressionError> {
+        let operators = [
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("<", Operator::Less),
+            ("<=", Operator::LessOrEqual),
+            (">", Operator::Greater),
+            (">=", Operator::GreaterOrEqual),
+        ];
+        let mut expression = Self::parse_sum(cursor)?;
+        while let Some(operator) = cursor.next_operator(&operators) {
+            expression = Self::binary(operator, expression, Self::parse_sum(cursor)?);
+        }
+        Ok(expression)
+    }
+
+    /// Parses operands separated by `+` or `-`.
+    fn parse_sum(cursor: &mut Cursor) -> Result<Expression, ExpressionError> {
+        let mut expression = Self::parse_product(cursor)?;
+        while let Some(operator) = cursor.next_operator(&[("+", Operator::Add), ("-", Operator::Subtract)]) {
+            expression = Self::binary(operator, expression, Self::parse_product(cursor)?);
+        }
+        Ok(expression)
+    }
+
+    /// Parses operands separated by `*` or `/`.
+    fn parse_product(cursor: &mut Cursor) -> Result<Expression, ExpressionError> {
+        let mut expression = Self::parse_unary(cursor)?;
+        while let Some(operator) = cursor.next_operator(&[("*", Operator::Multiply), ("/", Operator::Divide)]) {
+            expression = Self::binary(operator, expression, Self::parse_unary(cursor)?);
+        }
+        Ok(expression)
+    }
+
+    /// Parses an operand, optionally negated with `-` or `!`.
+    fn parse_unary(cursor: &mut Cursor) -> Result<Expression, ExpressionError> {
+        match cursor.peek() {
+            Some((Token::Symbol("-"), _)) => {
+                cursor.next += 1;
+                Ok(Expression::Negate(Box::new(Self::parse_unary(cursor)?)))
+            }
+            Some((Token::Symbol("!"), _)) => {
+                cursor.next += 1;
+                Ok(Expression::Not(Box::new(Self::parse_unary(cursor)?)))
+            }
+            _ => Self::parse_primary(cursor),
+        }
+    }
+
+    /// Parses a literal, an entity, a function call or an expression between parentheses.
+    fn parse_primary(cursor: &mut Cursor) -> Result<Expression, ExpressionError> {
+        let (token, position) = cursor
+            .peek()
+            .map(|(token, position)| (token.clone(), position))
+            .ok_or_else(|| Self::syntax_error(cursor.end, "expected an operand"))?;
+        cursor.next += 1;
+
+        match token {
+            Token::Number(number) if number.contains('.') => Decimal::parse(&number)
+                .map(|number| Expression::Literal(Value::Decimal(number)))
+                .map_err(|_| Self::syntax_error(position, &format!("invalid number {number}"))),
+            Token::Number(number) => number
+                .parse::<i128>()
+                .map(|number| Expression::Literal(Value::Integer(number)))
+                .map_err(|_| Self::syntax_error(position, &format!("invalid number {number}"))),
+            Token::Text(text) => Ok(Expression::Literal(Value::Text(text))),
+            Token::Name(name) if matches!(cursor.peek(), Some((Token::Symbol("("), _))) => {
+                cursor.next += 1;
+                let argument = match cursor.peek() {
+                    Some((Token::Name(argument), _)) => argument.to_string(),
+                    _ => return Err(Self::syntax_error(position, &format!("{name} expects an entity"))),
+                };
+                cursor.next += 1;
+                Self::close_parenthesis(cursor, position)?;
+                match name.as_str() {
+                    "is_set" => Ok(Expression::IsSet(argument)),
+                    _ => Err(Self::syntax_error(position, &format!("unknown function {name}"))),
+                }
+            }
+            Token::Name(name) => Ok(match name.as_str() {
+                "true" => Expression::Literal(Value::Bool(true)),
+                "false" => Expression::Literal(Value::Bool(false)),
+                _ => Expression::Entity(name),
+            }),
+            Token::Symbol("(") => {
+                let expression = Self::parse_or(cursor)?;
+                Self::close_parenthesis(cursor, position)?;
+                Ok(expression)
+            }
+            token => Err(Self::syntax_error(position, &format!("unexpected {}", Self::describe(&token)))),
+        }
+    }
+
+    /// Consumes the `)` closing the `(` found at `opening`.
+    fn close_parenthesis(cursor: &mut Cursor, opening: usize) -> Result<(), ExpressionError> {
+        match cursor.peek() {
+            Some((Token::Symbol(")"), _)) => {
+                cursor.next += 1;
+                Ok(())
+            }
+            Some((_, position)) => Err(Self::syntax_error(position, "expected ')'")),
+            None => Err(Self::syntax_error(opening, "unclosed '('")),
+        }
+    }
+
+    fn binary(operator: Operator, left: Expression, right: Expression) -> Expression {
+        Expression::Binary {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    fn describe(token: &Token) -> String {
+        match token {
+            Token::Number(number) => format!("number {number}"),
+            Token::Text(text) => format!("text \"{text}\""),
+            Token::Name(name) => format!("name {name}"),
+            Token::Symbol(symbol) => format!("'{symbol}'"),
+        }
+    }
+
+    fn syntax_error(position: usize, message: &str) -> ExpressionError {
+        ExpressionError::InvalidSyntax {
+            position,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Cursor {
+    fn peek(&self) -> Option<(&Token, usize)> {
+        self.tokens.get(self.next).map(|(token, position)| (token, *position))
+    }
+
+    /// Consumes the next token if it is one of the given operator symbols.
+    fn next_operator(&mut self, operators: &[(&str, Operator)]) -> Option<Operator> {
+        let operator = match self.peek() {
+            Some((Token::Symbol(symbol), _)) => operators
+                .iter()
+                .find(|(candidate, _)| candidate == symbol)
+                .map(|(_, operator)| *operator),
+            _ => None,
+        }?;
+        self.next += 1;
+        Some(operator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entity(name: &str) -> Box<Expression> {
+        Box::new(Expression::Entity(name.to_string()))
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        assert_eq!(
+            ExpressionParser::parse("prize - fee * 2").unwrap(),
+            Expression::Binary {
+                operator: Operator::Subtract,
+                left: entity("prize"),
+                right: Box::new(Expression::Binary {
+                    operator: Operator::Multiply,
+                    left: entity("fee"),
+                    right: Box::new(Expression::Literal(Value::Integer(2))),
+                }),
+            }
+        );
+        assert_eq!(
+            ExpressionParser::parse("(prize - fee) / -distance").unwrap(),
+            Expression::Binary {
+                operator: Operator::Divide,
+                left: Box::new(Expression::Binary {
+                    operator: Operator::Subtract,
+                    left: entity("prize"),
+                    right: entity("fee"),
+                }),
+                right: Box::new(Expression::Negate(entity("distance"))),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_literals() {
+        assert_eq!(ExpressionParser::parse("12.50").unwrap(), Expression::Literal(Value::Decimal(Decimal::new(1250, 2))));
+        assert_eq!(
+            ExpressionParser::parse(r#""say \"hi\"""#).unwrap(),
+            Expression::Literal(Value::Text("say \"hi\"".to_string()))
+        );
+        assert_eq!(ExpressionParser::parse(" true ").unwrap(), Expression::Literal(Value::Bool(true)));
+        assert_eq!(ExpressionParser::parse("is_open2").unwrap(), Expression::Entity("is_open2".to_string()));
+        assert_eq!(ExpressionParser::parse("is_set(ref_link)").unwrap(), Expression::IsSet("ref_link".to_string()));
+    }
+
+    #[test]
+    fn test_parse_logical_precedence() {
+        assert_eq!(
+            ExpressionParser::parse("status != \"published\" || is_set(ref_link) && !closed").unwrap(),
+            Expression::Binary {
+                operator: Operator::Or,
+                left: Box::new(Expression::Binary {
+                    operator: Operator::NotEqual,
+                    left: entity("status"),
+                    right: Box::new(Expression::Literal(Value::Text("published".to_string()))),
+                }),
+                right: Box::new(Expression::Binary {
+                    operator: Operator::And,
+                    left: Box::new(Expression::IsSet("ref_link".to_string())),
+                    right: Box::new(Expression::Not(entity("closed"))),
+                }),
+            }
+        );
+        assert_eq!(
+            ExpressionParser::parse("min_age + 2 <= max_age").unwrap(),
+            Expression::Binary {
+                operator: Operator::LessOrEqual,
+                left: Box::new(Expression::Binary {
+                    operator: Operator::Add,
+                    left: entity("min_age"),
+                    right: Box::new(Expression::Literal(Value::Integer(2))),
+                }),
+                right: entity("max_age"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_syntax() {
+        let error = |position: usize, message: &str| {
+            Err(ExpressionError::InvalidSyntax {
+                position,
+                message: message.to_string(),
+            })
+        };
+        assert_eq!(ExpressionParser::parse(""), error(0, "expected an operand"));
+        assert_eq!(ExpressionParser::parse("prize +"), error(7, "expected an operand"));
+        assert_eq!(ExpressionParser::parse("prize fee"), error(6, "unexpected name fee"));
+        assert_eq!(ExpressionParser::parse("(prize + 1"), error(0, "unclosed '('"));
+        assert_eq!(ExpressionParser::parse("prize)"), error(5, "unexpected ')'"));
+        assert_eq!(ExpressionParser::parse("prize % 2"), error(6, "unexpected character '%'"));
+        assert_eq!(ExpressionParser::parse("\"open"), error(0, "unterminated text"));
+        assert_eq!(ExpressionParser::parse("1.2.3"), error(0, "invalid number 1.2.3"));
+        assert_eq!(ExpressionParser::parse("prize = 2"), error(6, "unexpected character '='"));
+        assert_eq!(ExpressionParser::parse("is_set(1)"), error(0, "is_set expects an entity"));
+        assert_eq!(ExpressionParser::parse("is_empty(name)"), error(0, "unknown function is_empty"));
+    }
+}
@@ -0,0 +1,251 @@
+//! # Value
+//!
+//! Expressions compute with `Value`s, read from the `InstanceAttributes` of the entities they reference,
+//! and are type-checked with the `ValueType`s of their `MetaAttributes`.
+//!
+//! ## Conversions:
+//! - Integer attributes, whatever their width, are read as `Integer`, `F64` as `Float` and `Decimal` as `Decimal`.
+//! - `Text`, `Url`, `Email`, `Phone`, `Slug` and the keys of `Enum` attributes are read as `Text`.
+//! - `Date`, `DateTime` and `Time` attributes are read as `Date`, `DateTime` and `Time`, whatever their format.
+//! - Missing values are read as `Null`.
+//! - Computed values are written back as the type of the computed entity, integers widening to `Decimal` or `F64`.
+
+use std::{cmp::Ordering, fmt};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+
+use crate::core::{errors::AttributeError, instance::instance_entities::InstanceAttributes, meta::meta_entity::MetaAttributes, model::Decimal};
+
+/// A value an expression computes with.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Integer(i128),
+    Float(f64),
+    Decimal(Decimal),
+    Text(String),
+    Date(NaiveDate),
+    DateTime(DateTime<FixedOffset>),
+    Time(NaiveTime),
+}
+
+/// The type of a `Value`, known before evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Bool,
+    Integer,
+    Float,
+    Decimal,
+    Text,
+    Date,
+    DateTime,
+    Time,
+}
+
+impl ValueType {
+    /// Returns the type entities of the attribute are read as, `None` if expressions cannot read them.
+    pub fn of_attribute(attribute: &MetaAttributes) -> Option<ValueType> {
+        match attribute {
+            MetaAttributes::Bool => Some(ValueType::Bool),
+            attribute if attribute.is_integer() => Some(ValueType::Integer),
+            MetaAttributes::F64 => Some(ValueType::Float),
+            MetaAttributes::Decimal(_) => Some(ValueType::Decimal),
+            attribute if attribute.is_textual() => Some(ValueType::Text),
+            MetaAttributes::Enum(_) => Some(ValueType::Text),
+            MetaAttributes::Date(_) => Some(ValueType::Date),
+            MetaAttributes::DateTime(_) => Some(ValueType::DateTime),
+            MetaAttributes::Time(_) => Some(ValueType::Time),
+            _ => None,
+        }
+    }
+
+    /// Returns whether values of this type can be written to an entity of the attribute.
+    pub fn fits(&self, attribute: &MetaAttributes) -> bool {
+        match (&self, attribute) {
+            (ValueType::Bool, MetaAttributes::Bool) => true,
+            (ValueType::Integer, attribute) if attribute.is_integer() => true,
+            (ValueType::Integer | ValueType::Float | ValueType::Decimal, MetaAttributes::F64) => true,
+            (ValueType::Integer | ValueType::Decimal, MetaAttributes::Decimal(_)) => true,
+            (ValueType::Text, attribute) => attribute.is_textual(),
+            (ValueType::Date, MetaAttributes::Date(_)) => true,
+            (ValueType::DateTime, MetaAttributes::DateTime(_)) => true,
+            (ValueType::Time, MetaAttributes::Time(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns whether the type is `Integer`, `Float` or `Decimal`.
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, ValueType::Integer | ValueType::Float | ValueType::Decimal)
+    }
+}
+
+impl Value {
+    /// Reads the value of an entity.
+    pub fn from_attribute(value: &InstanceAttributes) -> Value {
+        if value.is_null() {
+            return Value::Null;
+        }
+
+        match (value, value.as_integer(), value.as_text()) {
+            (_, Some(integer), _) => Value::Integer(integer),
+            (_, _, Some(text)) => Value::Text(text.to_string()),
+            (InstanceAttributes::Bool(Some(boolean)), _, _) => Value::Bool(*boolean),
+            (InstanceAttributes::F64(Some(number)), _, _) => Value::Float(*number),
+            (InstanceAttributes::Decimal(Some(number)), _, _) => Value::Decimal(*number),
+            (InstanceAttributes::Enum(Some(choice)), _, _) => Value::Text(choice.get_key().to_string()),
+            (InstanceAttributes::Date(Some(date)), _, _) => Value::Date(*date),
+            (InstanceAttributes::DateTime(Some(datetime)), _, _) => Value::DateTime(*datetime),
+            (InstanceAttributes::Time(Some(time)), _, _) => Value::Time(*time),
+            _ => Value::Null,
+        }
+    }
+
+    /// Writes the value as the type of an entity, decimals being rounded to the scale of the entity.
+    pub fn to_attribute(&self, attribute: &MetaAttributes) -> Result<InstanceAttributes, AttributeError> {
+        match (&self, attribute) {
+            (Value::Null, attribute) => Ok(attribute.insert_none_for_type()),
+            (Value::Integer(integer), MetaAttributes::F64) => Ok(InstanceAttributes::F64(Some(*integer as f64))),
+            (Value::Decimal(number), MetaAttributes::F64) => Ok(InstanceAttributes::F64(Some(number.to_f64()))),
+            (Value::Integer(integer), MetaAttributes::Decimal(scale)) => {
+                Decimal::new(*integer, 0).rescale(*scale).map(|number| InstanceAttributes::Decimal(Some(number)))
+            }
+            (Value::Decimal(number), MetaAttributes::Decimal(scale)) => {
+                number.round(*scale).map(|number| InstanceAttributes::Decimal(Some(number)))
+            }
+            (Value::Date(date), MetaAttributes::Date(_)) => Ok(InstanceAttributes::Date(Some(*date))),
+            (Value::DateTime(datetime), MetaAttributes::DateTime(_)) => Ok(InstanceAttributes::DateTime(Some(*datetime))),
+            (Value::Time(time), MetaAttributes::Time(_)) => Ok(InstanceAttributes::Time(Some(*time))),
+            (value, attribute) => attribute.parse_attribute(Some(&value.to_string())),
+        }
+    }
+
+    /// Returns the type of the value, `None` for `Null`.
+    pub fn get_type(&self) -> Option<ValueType> {
+        match &self {
+            Value::Null => None,
+            Value::Bool(_) => Some(ValueType::Bool),
+            Value::Integer(_) => Some(ValueType::Integer),
+            Value::Float(_) => Some(ValueType::Float),
+            Value::Decimal(_) => Some(ValueType::Decimal),
+            Value::Text(_) => Some(ValueType::Text),
+            Value::Date(_) => Some(ValueType::Date),
+            Value::DateTime(_) => Some(ValueType::DateTime),
+            Value::Time(_) => Some(ValueType::Time),
+        }
+    }
+
+    /// Compares two values of the same type, numbers of different types as numbers, `None` if incomparable.
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (&self, other) {
+            (Value::Integer(left), Value::Integer(right)) => Some(left.cmp(right)),
+            (Value::Float(_), _) | (_, Value::Float(_)) => self.as_f64()?.partial_cmp(&other.as_f64()?),
+            (Value::Bool(left), Value::Bool(right)) => Some(left.cmp(right)),
+            (Value::Text(left), Value::Text(right)) => Some(left.cmp(right)),
+            (Value::Date(left), Value::Date(right)) => Some(left.cmp(right)),
+            (Value::DateTime(left), Value::DateTime(right)) => Some(left.cmp(right)),
+            (Value::Time(left), Value::Time(right)) => Some(left.cmp(right)),
+            _ => Some(self.as_decimal()?.cmp_value(&other.as_decimal()?)),
+        }
+    }
+
+    /// Returns the value as a `f64`, `None` for values that are not numbers.
+    pub fn as_f64(&self) -> Option<f64> {
+        match &self {
+            Value::Integer(integer) => Some(*integer as f64),
+            Value::Float(number) => Some(*number),
+            Value::Decimal(number) => Some(number.to_f64()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a `Decimal`, `None` for values that are neither integers nor decimals.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match &self {
+            Value::Integer(integer) => Some(Decimal::new(*integer, 0)),
+            Value::Decimal(number) => Some(*number),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Value::Null => write!(f, "Null"),
+            Value::Bool(boolean) => write!(f, "{}", boolean),
+            Value::Integer(integer) => write!(f, "{}", integer),
+            Value::Float(number) => write!(f, "{}", number),
+            Value::Decimal(number) => write!(f, "{}", number),
+            Value::Text(text) => write!(f, "{}", text),
+            Value::Date(date) => write!(f, "{}", date),
+            Value::DateTime(datetime) => write!(f, "{}", datetime.to_rfc3339()),
+            Value::Time(time) => write!(f, "{}", time),
+        }
+    }
+}
+
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            ValueType::Bool => write!(f, "bool"),
+            ValueType::Integer => write!(f, "integer"),
+            ValueType::Float => write!(f, "float"),
+            ValueType::Decimal => write!(f, "decimal"),
+            ValueType::Text => write!(f, "text"),
+            ValueType::Date => write!(f, "date"),
+            ValueType::DateTime => write!(f, "datetime"),
+            ValueType::Time => write!(f, "time"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_value_from_attribute() {
+        assert_eq!(Value::from_attribute(&InstanceAttributes::U64(Some(42))), Value::Integer(42));
+        assert_eq!(Value::from_attribute(&InstanceAttributes::I16(None)), Value::Null);
+        assert_eq!(
+            Value::from_attribute(&MetaAttributes::Slug.parse_attribute(Some("paris-2024")).unwrap()),
+            Value::Text("paris-2024".to_string())
+        );
+        assert_eq!(Value::from_attribute(&InstanceAttributes::List(Vec::new())), Value::Null);
+    }
+
+    #[test]
+    fn test_value_to_attribute() {
+        assert_eq!(Value::Integer(7).to_attribute(&MetaAttributes::I16), Ok(InstanceAttributes::I16(Some(7))));
+        assert!(Value::Integer(70_000).to_attribute(&MetaAttributes::I16).is_err());
+        assert_eq!(Value::Integer(7).to_attribute(&MetaAttributes::F64), Ok(InstanceAttributes::F64(Some(7.0))));
+        assert_eq!(
+            Value::Decimal(Decimal::new(10666, 3)).to_attribute(&MetaAttributes::Decimal(2)),
+            Ok(InstanceAttributes::Decimal(Some(Decimal::new(1067, 2))))
+        );
+        assert_eq!(Value::Null.to_attribute(&MetaAttributes::Text), Ok(InstanceAttributes::Text(None)));
+        assert!(Value::Text("paris".to_string()).to_attribute(&MetaAttributes::Url).is_err());
+    }
+
+    #[test]
+    fn test_value_types() {
+        assert_eq!(ValueType::of_attribute(&MetaAttributes::I64), Some(ValueType::Integer));
+        assert_eq!(ValueType::of_attribute(&MetaAttributes::Email), Some(ValueType::Text));
+        assert!(ValueType::of_attribute(&MetaAttributes::Duration).is_none());
+        assert!(ValueType::Integer.fits(&MetaAttributes::Decimal(2)));
+        assert!(!ValueType::Float.fits(&MetaAttributes::Decimal(2)));
+        assert!(!ValueType::Text.fits(&MetaAttributes::I32));
+        assert_eq!(ValueType::of_attribute(&MetaAttributes::Date(Some("%d/%m/%Y".to_string()))), Some(ValueType::Date));
+    }
+
+    #[test]
+    fn test_value_compare() {
+        let date = |input: &str| Value::from_attribute(&MetaAttributes::Date(None).parse_attribute(Some(input)).unwrap());
+        assert_eq!(date("2024-04-07").compare(&date("2024-04-06")), Some(Ordering::Greater));
+        assert_eq!(Value::Integer(2).compare(&Value::Decimal(Decimal::new(20, 1))), Some(Ordering::Equal));
+        assert_eq!(Value::Float(1.5).compare(&Value::Integer(2)), Some(Ordering::Less));
+        assert_eq!(Value::Text("a".to_string()).compare(&Value::Integer(1)), None);
+    }
+}
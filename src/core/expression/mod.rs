@@ -0,0 +1,38 @@
+//! # Expression Module
+//!
+//! The `Expression` module provides the small language computed entities and object rules are written in,
+//! e.g. a `full_title` computed as `name + " (" + year + ")"`, a `prize_per_km` computed as `prize / distance`
+//! or a rule `end_date >= start_date`.
+//!
+//! ## Core Components:
+//!
+//! ### `ExpressionParser`
+//! Turns the source of an expression into an `Expression` tree, rejecting malformed sources.
+//!
+//! ### `Expression`
+//! Lists the entities an expression reads, infers the type of the values it computes and evaluates it.
+//!
+//! ### `Value`
+//! The values expressions compute with, read from and written back to `InstanceAttributes`.
+//!
+//! ## Syntax:
+//! - Entities of the same object are referenced by name: `prize`.
+//! - Literals are integers `1000`, decimals `0.5`, texts `" km"` and booleans `true` / `false`.
+//! - Operators are `+`, `-`, `*`, `/` and the unary `-`, grouped with parentheses.
+//! - Comparisons are `==`, `!=`, `<`, `<=`, `>` and `>=`, combined with `&&`, `||` and `!`.
+//! - `is_set(entity)` tells whether an entity has a value, e.g. `status != "published" || is_set(ref_link)`.
+//!
+//! ## Typing:
+//! Expressions are checked when declared with `MetaObject::set_computed`, against the `MetaAttributes` of the
+//! entities they read and of the entity they compute, and again when `MetaObject::add_entity` changes the type
+//! of an entity they read, so that type errors never reach instances. Rules declared with `MetaObject::add_rule`
+//! must compute a boolean.
+//!
+//! ## Error Handling:
+//! The module defines `ExpressionError` for syntax errors, unknown or unsupported entities, operators applied
+//! to the wrong types, results the computed entity cannot hold, rules that do not compute a boolean, cycles
+//! between computed entities, and divisions by zero or overflows at evaluation time.
+
+pub mod expression_node;
+pub mod expression_parser;
+pub mod expression_value;
@@ -1,6 +1,10 @@
 use core::fmt;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
-use crate::core::{errors::AttributeError, model::entity::Entity};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+
+use crate::core::{errors::AttributeError, meta::meta_entity::{EnumChoice, MetaAttributes}, model::{entity::Entity, Currency, Decimal, Duration, Email, Money, Phone, Slug, UniqueId, Url}};
 
 
 pub type InstanceEntity = Entity<InstanceAttributes>;
@@ -9,9 +13,30 @@ pub type InstanceEntity = Entity<InstanceAttributes>;
 /// Enum representing the actual values for various attribute types in an instance object.
 ///
 /// These are initialized based on the meta attributes and populated with real data.
+/// Collections and objects are never missing, an empty collection or object standing for no value.
 pub enum InstanceAttributes {
     Text(Option<String>),
+    Bool(Option<bool>),
     I16(Option<i16>),
+    I32(Option<i32>),
+    I64(Option<i64>),
+    U64(Option<u64>),
+    F64(Option<f64>),
+    Decimal(Option<Decimal>),
+    Money(Option<Money>),
+    Date(Option<NaiveDate>),
+    DateTime(Option<DateTime<FixedOffset>>),
+    Time(Option<NaiveTime>),
+    Duration(Option<Duration>),
+    Url(Option<Url>),
+    Email(Option<Email>),
+    Phone(Option<Phone>),
+    Slug(Option<Slug>),
+    Enum(Option<EnumChoice>),
+    Object(BTreeMap<String, InstanceAttributes>),
+    Reference(Option<UniqueId>),
+    List(Vec<InstanceAttributes>),
+    Set(Vec<InstanceAttributes>),
 }
 
 impl fmt::Display for InstanceAttributes{
@@ -23,15 +48,160 @@ impl fmt::Display for InstanceAttributes{
                     None => write!(f,"Null"),
                 }
             },
+            InstanceAttributes::Bool(value) => Self::fmt_optional(f, value),
             InstanceAttributes::I16(value) => match value{
                 Some(i) => write!(f,"{}",i),
                 None => write!(f,"Null"),
             },
+            InstanceAttributes::I32(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::I64(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::U64(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::F64(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Decimal(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Money(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Date(value) => Self::fmt_optional(f, &value.map(|date| date.format("%Y-%m-%d"))),
+            InstanceAttributes::DateTime(value) => Self::fmt_optional(f, &value.map(|date_time| date_time.to_rfc3339())),
+            InstanceAttributes::Time(value) => Self::fmt_optional(f, &value.map(|time| time.format("%H:%M:%S"))),
+            InstanceAttributes::Duration(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Url(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Email(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Phone(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Slug(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::Enum(value) => Self::fmt_optional(f, &value.as_ref().map(EnumChoice::get_key)),
+            InstanceAttributes::Reference(value) => Self::fmt_optional(f, value),
+            InstanceAttributes::List(items) | InstanceAttributes::Set(items) => {
+                items.iter().enumerate().try_for_each(|(index, item)| match index {
+                    0 => write!(f, "{}", item),
+                    _ => write!(f, ", {}", item),
+                })
+            }
+            InstanceAttributes::Object(fields) => {
+                write!(f, "{{")?;
+                fields.iter().enumerate().try_for_each(|(index, (name, value))| match index {
+                    0 => write!(f, "{}: {}", name, value),
+                    _ => write!(f, ", {}: {}", name, value),
+                })?;
+                write!(f, "}}")
+            }
         }
     }
 }
 
 impl InstanceAttributes {
+    /// Returns whether the value is missing.
+    pub fn is_null(&self) -> bool {
+        match &self {
+            InstanceAttributes::Text(value) => value.is_none(),
+            InstanceAttributes::Bool(value) => value.is_none(),
+            InstanceAttributes::I16(value) => value.is_none(),
+            InstanceAttributes::I32(value) => value.is_none(),
+            InstanceAttributes::I64(value) => value.is_none(),
+            InstanceAttributes::U64(value) => value.is_none(),
+            InstanceAttributes::F64(value) => value.is_none(),
+            InstanceAttributes::Decimal(value) => value.is_none(),
+            InstanceAttributes::Money(value) => value.is_none(),
+            InstanceAttributes::Date(value) => value.is_none(),
+            InstanceAttributes::DateTime(value) => value.is_none(),
+            InstanceAttributes::Time(value) => value.is_none(),
+            InstanceAttributes::Duration(value) => value.is_none(),
+            InstanceAttributes::Url(value) => value.is_none(),
+            InstanceAttributes::Email(value) => value.is_none(),
+            InstanceAttributes::Phone(value) => value.is_none(),
+            InstanceAttributes::Slug(value) => value.is_none(),
+            InstanceAttributes::Enum(value) => value.is_none(),
+            InstanceAttributes::Reference(value) => value.is_none(),
+            InstanceAttributes::List(_) | InstanceAttributes::Set(_) | InstanceAttributes::Object(_) => false,
+        }
+    }
+
+    /// Returns whether the value counts as true in template conditions.
+    ///
+    /// `Text` is truthy when set and not empty, `Bool` when set and true, numbers and durations when set and
+    /// not zero, dates, times, validated strings, choices and references when set, collections when not empty, objects when one of their values is set.
+    pub fn is_truthy(&self) -> bool {
+        match &self {
+            InstanceAttributes::Text(value) => value.as_ref().is_some_and(|t| !t.is_empty()),
+            InstanceAttributes::Bool(value) => value.unwrap_or(false),
+            InstanceAttributes::I16(value) => value.is_some_and(|i| i != 0),
+            InstanceAttributes::I32(value) => value.is_some_and(|i| i != 0),
+            InstanceAttributes::I64(value) => value.is_some_and(|i| i != 0),
+            InstanceAttributes::U64(value) => value.is_some_and(|i| i != 0),
+            InstanceAttributes::F64(value) => value.is_some_and(|f| f != 0.0),
+            InstanceAttributes::Decimal(value) => value.is_some_and(|d| !d.is_zero()),
+            InstanceAttributes::Money(value) => value.is_some_and(|m| !m.get_amount().is_zero()),
+            InstanceAttributes::Date(value) => value.is_some(),
+            InstanceAttributes::DateTime(value) => value.is_some(),
+            InstanceAttributes::Time(value) => value.is_some(),
+            InstanceAttributes::Duration(value) => value.is_some_and(|d| !d.is_zero()),
+            InstanceAttributes::Url(value) => value.is_some(),
+            InstanceAttributes::Email(value) => value.is_some(),
+            InstanceAttributes::Phone(value) => value.is_some(),
+            InstanceAttributes::Slug(value) => value.is_some(),
+            InstanceAttributes::Enum(value) => value.is_some(),
+            InstanceAttributes::Reference(value) => value.is_some(),
+            InstanceAttributes::List(items) | InstanceAttributes::Set(items) => !items.is_empty(),
+            InstanceAttributes::Object(fields) => fields.values().any(|value| !value.is_null()),
+        }
+    }
+
+    /// Returns the elements of a collection value, `None` for single values.
+    pub fn get_items(&self) -> Option<&[InstanceAttributes]> {
+        match &self {
+            InstanceAttributes::List(items) | InstanceAttributes::Set(items) => Some(items),
+            InstanceAttributes::Text(_)
+            | InstanceAttributes::Bool(_)
+            | InstanceAttributes::I16(_)
+            | InstanceAttributes::I32(_)
+            | InstanceAttributes::I64(_)
+            | InstanceAttributes::U64(_)
+            | InstanceAttributes::F64(_)
+            | InstanceAttributes::Decimal(_)
+            | InstanceAttributes::Money(_)
+            | InstanceAttributes::Date(_)
+            | InstanceAttributes::DateTime(_)
+            | InstanceAttributes::Time(_)
+            | InstanceAttributes::Duration(_)
+            | InstanceAttributes::Url(_)
+            | InstanceAttributes::Email(_)
+            | InstanceAttributes::Phone(_)
+            | InstanceAttributes::Slug(_)
+            | InstanceAttributes::Enum(_)
+            | InstanceAttributes::Object(_)
+            | InstanceAttributes::Reference(_) => None,
+        }
+    }
+
+    /// Returns the value at a dotted path such as `address.city` within nested objects.
+    pub fn get_path(&self, path: &str) -> Option<&InstanceAttributes> {
+        path.split('.').try_fold(self, |value, field| match value {
+            InstanceAttributes::Object(fields) => fields.get(field),
+            _ => None,
+        })
+    }
+
+    /// Returns the string held by a `Text`, `Url`, `Email`, `Phone` or `Slug` value.
+    pub fn as_text(&self) -> Option<&str> {
+        match &self {
+            InstanceAttributes::Text(value) => value.as_deref(),
+            InstanceAttributes::Url(value) => value.as_ref().map(Url::as_str),
+            InstanceAttributes::Email(value) => value.as_ref().map(Email::as_str),
+            InstanceAttributes::Phone(value) => value.as_ref().map(Phone::as_str),
+            InstanceAttributes::Slug(value) => value.as_ref().map(Slug::as_str),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of an integer attribute, whatever its width.
+    pub fn as_integer(&self) -> Option<i128> {
+        match &self {
+            InstanceAttributes::I16(value) => value.map(i128::from),
+            InstanceAttributes::I32(value) => value.map(i128::from),
+            InstanceAttributes::I64(value) => value.map(i128::from),
+            InstanceAttributes::U64(value) => value.map(i128::from),
+            _ => None,
+        }
+    }
+
     /// Parses an optional string input into a `Text` attribute.
     pub fn parse_text(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
         match input {
@@ -42,14 +212,236 @@ impl InstanceAttributes {
 
     /// Parses an optional string input into an `I16` attribute.
     pub fn parse_i16(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
-        match input {
-            Some(i16) => i16
-                .parse::<i16>()
-                .map(|num| InstanceAttributes::I16(Some(num)))
-                .map_err(|_| AttributeError::InvalidType(format!("Expect i16 got {}", i16))),
-            None => Ok(InstanceAttributes::I16(None)),
+        Self::parse_number(input, "i16").map(InstanceAttributes::I16)
+    }
+
+    /// Parses an optional string input into a `Bool` attribute, accepting `true`/`false` in any case and `1`/`0`.
+    pub fn parse_bool(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        match input.map(str::trim) {
+            Some(value) if value.eq_ignore_ascii_case("true") || value == "1" => Ok(InstanceAttributes::Bool(Some(true))),
+            Some(value) if value.eq_ignore_ascii_case("false") || value == "0" => Ok(InstanceAttributes::Bool(Some(false))),
+            Some(value) => Err(AttributeError::InvalidType(format!("Expect bool got {}", value))),
+            None => Ok(InstanceAttributes::Bool(None)),
+        }
+    }
+
+    /// Parses an optional string input into an `I32` attribute.
+    pub fn parse_i32(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        Self::parse_number(input, "i32").map(InstanceAttributes::I32)
+    }
+
+    /// Parses an optional string input into an `I64` attribute.
+    pub fn parse_i64(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        Self::parse_number(input, "i64").map(InstanceAttributes::I64)
+    }
+
+    /// Parses an optional string input into a `U64` attribute.
+    pub fn parse_u64(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        Self::parse_number(input, "u64").map(InstanceAttributes::U64)
+    }
+
+    /// Parses an optional string input into an `F64` attribute, rejecting `NaN` and infinities.
+    pub fn parse_f64(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        match Self::parse_number::<f64>(input, "f64")? {
+            Some(value) if !value.is_finite() => {
+                Err(AttributeError::InvalidType(format!("Expect finite f64 got {}", value)))
+            }
+            value => Ok(InstanceAttributes::F64(value)),
+        }
+    }
+
+    /// Parses an optional string input into a `Decimal` attribute of the given scale.
+    pub fn parse_decimal(input: Option<&str>, scale: u32) -> Result<InstanceAttributes, AttributeError> {
+        input
+            .map(|decimal| Decimal::parse(decimal)?.rescale(scale))
+            .transpose()
+            .map(InstanceAttributes::Decimal)
+    }
+
+    /// Parses an optional string input into a `Money` attribute of the given currency.
+    pub fn parse_money(input: Option<&str>, currency: Currency) -> Result<InstanceAttributes, AttributeError> {
+        input
+            .map(|money| Money::parse(money, currency))
+            .transpose()
+            .map(InstanceAttributes::Money)
+    }
+
+    /// Parses an optional string input into a `Date` attribute, using `format` or `%Y-%m-%d`.
+    pub fn parse_date(input: Option<&str>, format: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        let format = format.unwrap_or("%Y-%m-%d");
+        input
+            .map(|date| {
+                NaiveDate::parse_from_str(date.trim(), format)
+                    .map_err(|_| AttributeError::InvalidType(format!("Expect date as {} got {}", format, date)))
+            })
+            .transpose()
+            .map(InstanceAttributes::Date)
+    }
+
+    /// Parses an optional string input into a `DateTime` attribute, using `format`, which must hold the
+    /// timezone offset, or RFC 3339.
+    pub fn parse_datetime(input: Option<&str>, format: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        input
+            .map(|date_time| {
+                let parsed = match format {
+                    Some(format) => DateTime::parse_from_str(date_time.trim(), format),
+                    None => DateTime::parse_from_rfc3339(date_time.trim()),
+                };
+                parsed.map_err(|_| {
+                    AttributeError::InvalidType(format!("Expect datetime as {} got {}", format.unwrap_or("RFC 3339"), date_time))
+                })
+            })
+            .transpose()
+            .map(InstanceAttributes::DateTime)
+    }
+
+    /// Parses an optional string input into a `Time` attribute, using `format`, or `%H:%M:%S` and `%H:%M`.
+    pub fn parse_time(input: Option<&str>, format: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        let formats = format.map_or(vec!["%H:%M:%S", "%H:%M"], |format| vec![format]);
+        input
+            .map(|time| {
+                formats
+                    .iter()
+                    .find_map(|format| NaiveTime::parse_from_str(time.trim(), format).ok())
+                    .ok_or_else(|| AttributeError::InvalidType(format!("Expect time as {} got {}", formats[0], time)))
+            })
+            .transpose()
+            .map(InstanceAttributes::Time)
+    }
+
+    /// Parses an optional string input into a `Duration` attribute.
+    pub fn parse_duration(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        input
+            .map(Duration::parse)
+            .transpose()
+            .map(InstanceAttributes::Duration)
+    }
+
+    /// Parses an optional string input into a `Url` attribute.
+    pub fn parse_url(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        input.map(Url::parse).transpose().map(InstanceAttributes::Url)
+    }
+
+    /// Parses an optional string input into an `Email` attribute.
+    pub fn parse_email(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        input.map(Email::parse).transpose().map(InstanceAttributes::Email)
+    }
+
+    /// Parses an optional string input into a `Phone` attribute, normalized to the E.164 format.
+    pub fn parse_phone(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        input.map(Phone::parse).transpose().map(InstanceAttributes::Phone)
+    }
+
+    /// Parses an optional string input into a `Slug` attribute.
+    pub fn parse_slug(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        input.map(Slug::parse).transpose().map(InstanceAttributes::Slug)
+    }
+
+    /// Parses an optional string input into an `Enum` attribute, the input being the key of one of the choices.
+    pub fn parse_enum(input: Option<&str>, choices: &[EnumChoice]) -> Result<InstanceAttributes, AttributeError> {
+        input
+            .map(|key| {
+                choices
+                    .iter()
+                    .find(|choice| choice.get_key() == key.trim())
+                    .cloned()
+                    .ok_or_else(|| AttributeError::NotAllowed {
+                        value: key.to_string(),
+                        allowed: choices.iter().map(|choice| choice.get_key().to_string()).collect(),
+                    })
+            })
+            .transpose()
+            .map(InstanceAttributes::Enum)
+    }
+
+    /// Parses an optional string input into a `Reference` attribute, the input being the id of the referenced instance.
+    pub fn parse_reference(input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        input
+            .map(|id| {
+                id.parse::<UniqueId>()
+                    .map_err(|_| AttributeError::InvalidType(format!("Expect an instance id got {}", id)))
+            })
+            .transpose()
+            .map(InstanceAttributes::Reference)
+    }
+
+    fn parse_number<T: FromStr>(input: Option<&str>, type_name: &str) -> Result<Option<T>, AttributeError> {
+        input
+            .map(|number| {
+                number
+                    .trim()
+                    .parse::<T>()
+                    .map_err(|_| AttributeError::InvalidType(format!("Expect {} got {}", type_name, number)))
+            })
+            .transpose()
+    }
+
+    fn fmt_optional<T: fmt::Display>(f: &mut fmt::Formatter<'_>, value: &Option<T>) -> fmt::Result {
+        match value {
+            Some(v) => write!(f, "{}", v),
+            None => write!(f, "Null"),
         }
     }
+
+    /// Parses a comma-separated input into a `List` attribute, each element being parsed as `element`.
+    ///
+    /// A comma inside an element is written `\,` and a backslash before a comma `\\`, e.g. `Paris\, France, Lyon`.
+    /// A missing or blank input gives an empty list.
+    pub fn parse_list(element: &MetaAttributes, input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        Self::parse_items(element, input).map(InstanceAttributes::List)
+    }
+
+    /// Parses a comma-separated input into a `Set` attribute, keeping the first occurrence of duplicated elements.
+    pub fn parse_set(element: &MetaAttributes, input: Option<&str>) -> Result<InstanceAttributes, AttributeError> {
+        Self::parse_items(element, input).map(|items| {
+            let mut unique: Vec<InstanceAttributes> = Vec::with_capacity(items.len());
+            items.into_iter().for_each(|item| {
+                if !unique.contains(&item) {
+                    unique.push(item);
+                }
+            });
+            InstanceAttributes::Set(unique)
+        })
+    }
+
+    fn parse_items(element: &MetaAttributes, input: Option<&str>) -> Result<Vec<InstanceAttributes>, AttributeError> {
+        let Some(input) = input.filter(|input| !input.trim().is_empty()) else {
+            return Ok(Vec::new());
+        };
+
+        Self::split_items(input)
+            .iter()
+            .map(|item| item.trim())
+            .enumerate()
+            .map(|(index, item)| {
+                let parsed = match item.is_empty() {
+                    true => Err(AttributeError::InvalidType("Expect a value got an empty element".to_string())),
+                    false => element.parse_attribute(Some(item)),
+                };
+                parsed.map_err(|error| AttributeError::InvalidElement { index, error: Box::new(error) })
+            })
+            .collect()
+    }
+
+    /// Splits a comma-separated input, `\,` standing for a comma and `\\` for a backslash inside an element.
+    /// Other backslashes are kept as they are.
+    fn split_items(input: &str) -> Vec<String> {
+        let mut items = Vec::new();
+        let mut item = String::new();
+        let mut chars = input.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('\\', Some(&escaped @ (',' | '\\'))) => {
+                    chars.next();
+                    item.push(escaped);
+                }
+                (',', _) => items.push(std::mem::take(&mut item)),
+                (c, _) => item.push(c),
+            }
+        }
+        items.push(item);
+        items
+    }
 }
 
 
@@ -96,6 +488,73 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_integers_trim_their_input() {
+        assert_eq!(InstanceAttributes::parse_i16(Some(" 5 ")), Ok(InstanceAttributes::I16(Some(5))));
+        assert_eq!(InstanceAttributes::parse_i32(Some(" 5 ")), Ok(InstanceAttributes::I32(Some(5))));
+        assert_eq!(InstanceAttributes::parse_u64(Some(" 5 ")), Ok(InstanceAttributes::U64(Some(5))));
+    }
+
+    #[test]
+    fn test_is_null() {
+        assert!(InstanceAttributes::Text(None).is_null());
+        assert!(!InstanceAttributes::Text(Some(String::new())).is_null());
+        assert!(InstanceAttributes::I16(None).is_null());
+        assert!(!InstanceAttributes::I16(Some(0)).is_null());
+    }
+
+    #[test]
+    fn test_is_truthy() {
+        assert!(InstanceAttributes::Text(Some("value".to_string())).is_truthy());
+        assert!(!InstanceAttributes::Text(Some(String::new())).is_truthy());
+        assert!(!InstanceAttributes::Text(None).is_truthy());
+        assert!(InstanceAttributes::I16(Some(-3)).is_truthy());
+        assert!(!InstanceAttributes::I16(Some(0)).is_truthy());
+        assert!(!InstanceAttributes::I16(None).is_truthy());
+    }
+
+    #[test]
+    fn test_display_numeric_family() {
+        assert_eq!(InstanceAttributes::Bool(Some(true)).to_string(), "true");
+        assert_eq!(InstanceAttributes::I32(Some(40000)).to_string(), "40000");
+        assert_eq!(InstanceAttributes::U64(Some(u64::MAX)).to_string(), "18446744073709551615");
+        assert_eq!(InstanceAttributes::F64(Some(42.195)).to_string(), "42.195");
+        assert_eq!(InstanceAttributes::I64(None).to_string(), "Null");
+        assert!(!InstanceAttributes::Bool(Some(false)).is_truthy());
+        assert!(InstanceAttributes::F64(Some(0.5)).is_truthy());
+        assert_eq!(InstanceAttributes::U64(Some(7)).as_integer(), Some(7));
+        assert_eq!(InstanceAttributes::F64(Some(7.0)).as_integer(), None);
+    }
+
+    #[test]
+    fn test_object_paths() {
+        let address = InstanceAttributes::Object(BTreeMap::from([
+            ("city".to_string(), InstanceAttributes::Text(Some("Paris".to_string()))),
+            ("postcode".to_string(), InstanceAttributes::I32(None)),
+        ]));
+        let event = InstanceAttributes::Object(BTreeMap::from([("address".to_string(), address.clone())]));
+
+        assert_eq!(event.get_path("address.city"), Some(&InstanceAttributes::Text(Some("Paris".to_string()))));
+        assert_eq!(event.get_path("address.country"), None);
+        assert_eq!(event.get_path("address.city.name"), None);
+        assert_eq!(address.to_string(), "{city: Paris, postcode: Null}");
+        assert!(address.is_truthy());
+        assert!(!InstanceAttributes::Object(BTreeMap::new()).is_truthy());
+    }
+
+    #[test]
+    fn test_display_collections() {
+        let list = InstanceAttributes::List(vec![
+            InstanceAttributes::Text(Some("Ada".to_string())),
+            InstanceAttributes::Text(Some("Grace".to_string())),
+        ]);
+        assert_eq!(list.to_string(), "Ada, Grace");
+        assert_eq!(InstanceAttributes::Set(Vec::new()).to_string(), "");
+        assert!(!InstanceAttributes::List(Vec::new()).is_null());
+        assert!(!InstanceAttributes::List(Vec::new()).is_truthy());
+        assert!(list.is_truthy());
+    }
+
     #[test]
     fn test_parse_i16_none() {
         let result = InstanceAttributes::parse_i16(None);
@@ -1,8 +1,10 @@
-use std::{collections::HashMap, marker::PhantomData};
+use std::{collections::{BTreeMap, HashMap}, marker::PhantomData};
 
-use crate::core::{errors::{AttributeError, UniqueIdError}, meta::{meta_entity::MetaEntity, meta_object::MetaObject}, model::{entity::Entity, object::Object, unique_id::Identifier, UniqueId}};
+use crate::core::{errors::{AttributeError, ExpressionError, ObjectError, UniqueIdError}, expression::expression_value::Value, meta::{meta_entity::{MetaAttributes, MetaEntity}, meta_object::MetaObject, meta_rule::Rule}, model::{entity::Entity, object::Object, unique_id::Identifier, UniqueId}};
+use crate::storage::in_memory::object_collections::{InstanceObjectCollection, MetaObjectCollection};
 
 use super::instance_entities::{InstanceAttributes, InstanceEntity};
+use super::instance_validation::ValidationReport;
 
 use crate::core::model::entity::EntityTraits;
 
@@ -23,6 +25,7 @@ impl InstanceObject {
                 entities,
                 id: unique_id,
                 meta_id: Some(meta_id.clone()),
+                extension: (),
                 _marker: PhantomData,
             }),
             Err(err) => Err(err),
@@ -35,6 +38,10 @@ pub struct InstanceObjectBuilder {
     meta_entities: HashMap<String, MetaEntity>,
     instance_entities: HashMap<String, InstanceEntity>,
     meta_id: UniqueId,
+    nested_meta_entities: HashMap<UniqueId, HashMap<String, MetaEntity>>,
+    nested_rules: HashMap<UniqueId, Vec<Rule>>,
+    instance_meta_ids: Option<HashMap<UniqueId, UniqueId>>,
+    rules: Vec<Rule>,
 }
 
 impl InstanceObjectBuilder {
@@ -47,50 +54,496 @@ impl InstanceObjectBuilder {
             meta_entities: object_cloned.entities,
             instance_entities: HashMap::new(),
             meta_id: object_cloned_id,
+            nested_meta_entities: HashMap::new(),
+            nested_rules: HashMap::new(),
+            instance_meta_ids: None,
+            rules: object.get_rules().to_vec(),
         }
     }
 
-    /// Updates an instance entity with a parsed value from a meta entity.
+    /// Loads the embedded `MetaObject`s and their rules, rejecting unknown, self-embedding or computed ones.
+    pub fn with_meta_objects(mut self, meta_objects: &MetaObjectCollection) -> Result<Self, AttributeError> {
+        let mut pending: Vec<UniqueId> = self
+            .meta_entities
+            .values()
+            .filter_map(|entity| Self::embedded_meta_id(entity.get_attribute()))
+            .collect();
+
+        while let Some(meta_id) = pending.pop() {
+            if self.nested_meta_entities.contains_key(&meta_id) {
+                continue;
+            }
+            let meta_object = meta_objects
+                .get(&meta_id)
+                .map_err(|_| AttributeError::UnknownMetaObject(meta_id.to_string()))?;
+            if let Some(computed) = meta_object.get_computation_order().first() {
+                return Err(AttributeError::EmbeddedComputedEntity(format!("{}.{}", meta_object.name, computed)));
+            }
+            pending.extend(
+                meta_object
+                    .entities
+                    .values()
+                    .filter_map(|entity| Self::embedded_meta_id(entity.get_attribute())),
+            );
+            self.nested_rules.insert(meta_id.clone(), meta_object.get_rules().to_vec());
+            self.nested_meta_entities.insert(meta_id, meta_object.entities.clone());
+        }
+
+        match self.find_embedding_cycle(&self.meta_entities, &mut vec![&self.meta_id]) {
+            Some(meta_id) => Err(AttributeError::CyclicObject(meta_id.to_string())),
+            None => Ok(self),
+        }
+    }
+
+    /// Returns the id of a `MetaObject` embedding itself through `entities`, `path` holding the embedding ids.
+    fn find_embedding_cycle<'m>(&'m self, entities: &'m HashMap<String, MetaEntity>, path: &mut Vec<&'m UniqueId>) -> Option<&'m UniqueId> {
+        entities.values().find_map(|entity| {
+            let MetaAttributes::Object(meta_id) = entity.get_attribute() else {
+                return None;
+            };
+            if path.contains(&meta_id) {
+                return Some(meta_id);
+            }
+            let nested = self.nested_meta_entities.get(meta_id)?;
+            path.push(meta_id);
+            let cycle = self.find_embedding_cycle(nested, path);
+            path.pop();
+            cycle
+        })
+    }
+
+    /// Gives the instances `Reference` entities may point at, which `build` requires when a reference is set.
+    pub fn with_instance_objects(mut self, instance_objects: &InstanceObjectCollection) -> Self {
+        self.instance_meta_ids = Some(
+            instance_objects
+                .iter()
+                .filter_map(|object| object.get_meta_id().clone().map(|meta_id| (object.get_id().clone(), meta_id)))
+                .collect(),
+        );
+        self
+    }
+
+    /// Updates an instance entity, or a nested field by dotted path, then recomputes and checks the rules.
     pub fn update_entity(
         &mut self,
         entity_name: &str,
         input: Option<&str>,
     ) -> Result<(), AttributeError> {
+        let previous = self.snapshot([entity_name]);
+        self.set_entity(entity_name, input)?;
+
+        let checked = match self.recompute().into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => self.check_rules(),
+        };
+        checked.inspect_err(|_| self.restore(previous))
+    }
+
+    /// Updates several entities from raw inputs, reporting every error and undoing all of them on error.
+    pub fn update_entities(&mut self, inputs: &HashMap<String, String>) -> Result<(), ValidationReport> {
+        let previous = self.snapshot(inputs.keys().map(String::as_str));
+        let mut report = ValidationReport::default();
+
+        let mut names = inputs.keys().collect::<Vec<&String>>();
+        names.sort();
+        names.into_iter().for_each(|name| {
+            if let Err(error) = self.set_entity(name, Some(&inputs[name])) {
+                report.add_entity_error(name, error);
+            }
+        });
+
+        self.recompute()
+            .into_iter()
+            .for_each(|(entity, error)| report.add_entity_error(&entity, error));
+        self.broken_rules().into_iter().for_each(|(rule, error)| report.add_rule_error(&rule, error));
+
+        match report.is_empty() {
+            true => Ok(()),
+            false => {
+                self.restore(previous);
+                Err(report)
+            }
+        }
+    }
+
+    /// Returns the current values of the entities an update of `names` may change, computed ones included.
+    fn snapshot<'n>(&self, names: impl IntoIterator<Item = &'n str>) -> Vec<(String, Option<InstanceEntity>)> {
+        let mut touched = names
+            .into_iter()
+            .map(|name| name.split_once('.').map_or(name, |(root, _)| root))
+            .chain(
+                self.meta_entities
+                    .values()
+                    .filter(|entity| entity.is_computed())
+                    .map(|entity| entity.get_name()),
+            )
+            .collect::<Vec<&str>>();
+        touched.sort();
+        touched.dedup();
+        touched
+            .into_iter()
+            .map(|name| (name.to_string(), self.instance_entities.get(name).cloned()))
+            .collect()
+    }
+
+    /// Puts back the values of a `snapshot`, removing the entities that had none.
+    fn restore(&mut self, snapshot: Vec<(String, Option<InstanceEntity>)>) {
+        snapshot.into_iter().for_each(|(name, entity)| match entity {
+            Some(entity) => {
+                self.instance_entities.insert(name, entity);
+            }
+            None => {
+                self.instance_entities.remove(&name);
+            }
+        });
+    }
+
+    /// Parses, checks and stores the value of an entity or of a nested field given by dotted path.
+    fn set_entity(&mut self, entity_name: &str, input: Option<&str>) -> Result<(), AttributeError> {
+        let (root, path) = match entity_name.split_once('.') {
+            Some((root, path)) => (root, Some(path)),
+            None => (entity_name, None),
+        };
 
         let metat_entity = &self
             .meta_entities
-            .get(entity_name)
-            .ok_or_else(|| AttributeError::NonMatchingType("tt".to_string()))?;
+            .get(root)
+            .ok_or_else(|| AttributeError::UnknownEntity(entity_name.to_string()))?;
+        if metat_entity.is_computed() {
+            return Err(AttributeError::ReadOnlyEntity(root.to_string()));
+        }
 
-        let instance = metat_entity.get_attribute().parse_attribute(input)?;
+        let instance = match path {
+            Some(path) => {
+                let current = self.instance_entities.get(root).map(|entity| entity.get_attribute().clone());
+                self.update_path(metat_entity.get_attribute(), current, path, input)
+                    .map_err(|error| match error {
+                        AttributeError::UnknownEntity(_) => AttributeError::UnknownEntity(entity_name.to_string()),
+                        error => error,
+                    })?
+            }
+            None => metat_entity.get_attribute().parse_attribute(input)?,
+        };
+        metat_entity.check(&instance)?;
+        self.check_references(metat_entity.get_attribute(), &instance)?;
 
         self.instance_entities.insert(
-            entity_name.to_string(),
-            InstanceEntity::new(entity_name, instance),
+            root.to_string(),
+            InstanceEntity::new(root, instance),
         );
-
         Ok(())
     }
 
-    /// Populates missing meta-entities in the instance object with default values.
-    pub fn populate_missing_meta_entites(&mut self) {
-        let _ = &self.meta_entities.iter().for_each(|(k, v)| {
-            if let None = &self.instance_entities.get(k.as_str()) {
-                let attribute = v.get_attribute().insert_none_for_type();
-                let name = k.clone();
-                self.instance_entities
-                    .insert(k.to_string(), InstanceEntity::new(&name, attribute));
+    /// Returns the error of the first rule broken by the values, nested objects included.
+    fn check_rules(&self) -> Result<(), AttributeError> {
+        match self.broken_rules().into_iter().next() {
+            Some((_, error)) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the broken rules with their error, nested ones named by dotted path such as `address.zip_format`.
+    fn broken_rules(&self) -> Vec<(String, AttributeError)> {
+        let mut broken = Vec::new();
+        self.collect_broken_rules(
+            &self.rules,
+            &self.meta_entities,
+            &|name| self.instance_entities.get(name).map(|instance| instance.get_attribute()),
+            "",
+            &mut broken,
+        );
+        broken
+    }
+
+    /// Adds the rules broken by the values, then by the nested objects, to `broken`, prefixing their names.
+    fn collect_broken_rules<'v>(
+        &self,
+        rules: &[Rule],
+        entities: &HashMap<String, MetaEntity>,
+        values: &dyn Fn(&str) -> Option<&'v InstanceAttributes>,
+        prefix: &str,
+        broken: &mut Vec<(String, AttributeError)>,
+    ) {
+        rules.iter().for_each(|rule| {
+            let error = match rule.check(&|name| values(name).map_or(Value::Null, Value::from_attribute)) {
+                Ok(()) => return,
+                Err(AttributeError::RuleViolation { rule, entities }) => AttributeError::RuleViolation {
+                    rule: format!("{prefix}{rule}"),
+                    entities: entities.iter().map(|entity| format!("{prefix}{entity}")).collect(),
+                },
+                Err(AttributeError::InvalidRule { rule, error }) => AttributeError::InvalidRule {
+                    rule: format!("{prefix}{rule}"),
+                    error,
+                },
+                Err(error) => error,
+            };
+            broken.push((format!("{}{}", prefix, rule.get_name()), error));
+        });
+
+        let mut nested = entities
+            .values()
+            .filter_map(|entity| match (entity.get_attribute(), values(entity.get_name())) {
+                (MetaAttributes::Object(meta_id), Some(InstanceAttributes::Object(fields))) => Some((entity.get_name(), meta_id, fields)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        nested.sort_by_key(|(name, _, _)| *name);
+        nested.into_iter().for_each(|(name, meta_id, fields)| {
+            if let (Some(rules), Some(entities)) = (self.nested_rules.get(meta_id), self.nested_meta_entities.get(meta_id)) {
+                self.collect_broken_rules(rules, entities, &|field| fields.get(field), &format!("{prefix}{name}."), broken);
+            }
+        });
+    }
+
+    /// Computes the computed entities in order, returning those that fail with their error.
+    fn recompute(&mut self) -> Vec<(String, AttributeError)> {
+        let order = match MetaObject::computation_order(&self.meta_entities) {
+            Ok(order) => order,
+            Err(error) => {
+                let entity = match &error {
+                    ExpressionError::CyclicComputation(cycle) => cycle.first().cloned().unwrap_or_default(),
+                    _ => String::new(),
+                };
+                let error = AttributeError::ComputationFailed {
+                    entity: entity.clone(),
+                    error,
+                };
+                return vec![(entity, error)];
+            }
+        };
+
+        let mut failed = Vec::new();
+        for name in order {
+            let entity = &self.meta_entities[name];
+            let Some(expression) = entity.get_expression() else {
+                continue;
+            };
+            let value = expression
+                .evaluate(&|reference| {
+                    self.instance_entities
+                        .get(reference)
+                        .map_or(Value::Null, |instance| Value::from_attribute(instance.get_attribute()))
+                })
+                .map_err(|error| AttributeError::ComputationFailed {
+                    entity: name.to_string(),
+                    error,
+                })
+                .and_then(|value| value.to_attribute(entity.get_attribute()));
+            match value {
+                Ok(value) => {
+                    self.instance_entities.insert(name.to_string(), InstanceEntity::new(name, value));
+                }
+                Err(error) => {
+                    self.instance_entities.remove(name);
+                    failed.push((name.to_string(), error));
+                }
             }
+        }
+        failed
+    }
+
+    /// Populates missing meta-entities with their default value, or an empty one, leaving required ones missing.
+    pub fn populate_missing_meta_entites(&mut self) {
+        let populated: Vec<(String, InstanceAttributes)> = self
+            .meta_entities
+            .iter()
+            .filter(|(_, entity)| !entity.is_computed())
+            .filter_map(|(name, entity)| {
+                let value = match (self.instance_entities.get(name), entity.get_default()) {
+                    (Some(instance), _) => instance.get_attribute().clone(),
+                    (None, Some(default)) => default.clone(),
+                    (None, None) if entity.is_required() => return None,
+                    (None, None) => entity.get_attribute().insert_none_for_type(),
+                };
+                Some((name.to_string(), self.populate_missing_fields(entity.get_attribute(), value)))
+            })
+            .collect();
+
+        populated.into_iter().for_each(|(name, value)| {
+            self.instance_entities.insert(name.to_string(), InstanceEntity::new(&name, value));
         });
     }
 
-    /// Builds and returns the final `InstanceObject`.
-    pub fn build(self) -> Result<InstanceObject, UniqueIdError> {
-        InstanceObject::new_instance(
+    /// Returns the nested object `current` with the value at `path` parsed from `input`.
+    fn update_path(
+        &self,
+        attribute: &MetaAttributes,
+        current: Option<InstanceAttributes>,
+        path: &str,
+        input: Option<&str>,
+    ) -> Result<InstanceAttributes, AttributeError> {
+        let MetaAttributes::Object(meta_id) = attribute else {
+            return Err(AttributeError::InvalidType(format!("Expect an object to set {} got {:?}", path, attribute)));
+        };
+        let entities = self
+            .nested_meta_entities
+            .get(meta_id)
+            .ok_or_else(|| AttributeError::UnknownMetaObject(meta_id.to_string()))?;
+
+        let (field, rest) = match path.split_once('.') {
+            Some((field, rest)) => (field, Some(rest)),
+            None => (path, None),
+        };
+        let field_entity = entities
+            .get(field)
+            .ok_or_else(|| AttributeError::UnknownEntity(field.to_string()))?;
+        if field_entity.is_computed() {
+            return Err(AttributeError::ReadOnlyEntity(field.to_string()));
+        }
+
+        let mut fields = match current {
+            Some(InstanceAttributes::Object(fields)) => fields,
+            _ => BTreeMap::new(),
+        };
+        let value = match rest {
+            Some(rest) => self.update_path(field_entity.get_attribute(), fields.remove(field), rest, input)?,
+            None => field_entity.get_attribute().parse_attribute(input)?,
+        };
+        field_entity.check(&value)?;
+        fields.insert(field.to_string(), value);
+        Ok(InstanceAttributes::Object(fields))
+    }
+
+    /// Fills the missing entities of a nested object, recursively.
+    fn populate_missing_fields(&self, attribute: &MetaAttributes, value: InstanceAttributes) -> InstanceAttributes {
+        let (MetaAttributes::Object(meta_id), InstanceAttributes::Object(mut fields)) = (attribute, value.clone()) else {
+            return value;
+        };
+        let Some(entities) = self.nested_meta_entities.get(meta_id) else {
+            return value;
+        };
+
+        entities.iter().for_each(|(name, entity)| {
+            let field = match (fields.remove(name), entity.get_default()) {
+                (Some(field), _) => field,
+                (None, Some(default)) => default.clone(),
+                (None, None) if entity.is_required() => return,
+                (None, None) => entity.get_attribute().insert_none_for_type(),
+            };
+            fields.insert(name.to_string(), self.populate_missing_fields(entity.get_attribute(), field));
+        });
+        InstanceAttributes::Object(fields)
+    }
+
+    /// Checks that the referenced instances, nested ones included, are known instances of the referenced `MetaObject`.
+    fn check_references(&self, attribute: &MetaAttributes, value: &InstanceAttributes) -> Result<(), AttributeError> {
+        match (attribute, value) {
+            (MetaAttributes::Reference(meta_id), InstanceAttributes::Reference(Some(id))) => match &self.instance_meta_ids {
+                Some(instance_meta_ids) if instance_meta_ids.get(id) != Some(meta_id) => Err(AttributeError::InvalidReference {
+                    id: id.to_string(),
+                    meta: meta_id.to_string(),
+                }),
+                _ => Ok(()),
+            },
+            (MetaAttributes::List(element) | MetaAttributes::Set(element), InstanceAttributes::List(items) | InstanceAttributes::Set(items)) => {
+                items.iter().try_for_each(|item| self.check_references(element, item))
+            }
+            (MetaAttributes::Object(meta_id), InstanceAttributes::Object(fields)) => {
+                let Some(entities) = self.nested_meta_entities.get(meta_id) else {
+                    return Ok(());
+                };
+                fields.iter().try_for_each(|(name, field)| match entities.get(name) {
+                    Some(entity) => self.check_references(entity.get_attribute(), field),
+                    None => Ok(()),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the id of the `MetaObject` embedded by an attribute, directly or as collection elements.
+    fn embedded_meta_id(attribute: &MetaAttributes) -> Option<UniqueId> {
+        match attribute {
+            MetaAttributes::Object(meta_id) => Some(meta_id.clone()),
+            attribute => attribute.get_element_type().and_then(Self::embedded_meta_id),
+        }
+    }
+
+    /// Returns the paths of the required entities without value, nested objects and empty collections included.
+    fn missing_required<'v>(
+        &self,
+        entities: &HashMap<String, MetaEntity>,
+        values: &dyn Fn(&str) -> Option<&'v InstanceAttributes>,
+        prefix: &str,
+    ) -> Vec<String> {
+        entities
+            .values()
+            .flat_map(|entity| {
+                let path = format!("{}{}", prefix, entity.get_name());
+                let value = values(entity.get_name()).filter(|value| !value.is_null());
+                let is_set = value.is_some_and(InstanceAttributes::is_truthy);
+                match (entity.get_attribute(), value) {
+                    (MetaAttributes::Object(meta_id), value) if entity.is_required() || is_set => {
+                        let empty = BTreeMap::new();
+                        let fields = match value {
+                            Some(InstanceAttributes::Object(fields)) => fields,
+                            _ => &empty,
+                        };
+                        let missing = self
+                            .nested_meta_entities
+                            .get(meta_id)
+                            .map(|nested| self.missing_required(nested, &|name| fields.get(name), &format!("{path}.")))
+                            .unwrap_or_default();
+                        match missing.is_empty() && !is_set {
+                            true => vec![path],
+                            false => missing,
+                        }
+                    }
+                    (MetaAttributes::List(_) | MetaAttributes::Set(_), _) if entity.is_required() && !is_set => vec![path],
+                    (_, None) if entity.is_required() => vec![path],
+                    _ => Vec::new(),
+                }
+            })
+            .collect()
+    }
+
+    /// Checks a value, and the fields of its nested objects, against the constraints of their `MetaEntity`.
+    fn check_constraints(&self, entity: &MetaEntity, value: Option<&InstanceAttributes>) -> Result<(), AttributeError> {
+        let value = value.cloned().unwrap_or_else(|| entity.get_attribute().insert_none_for_type());
+        entity.check(&value)?;
+
+        match (entity.get_attribute(), &value) {
+            (MetaAttributes::Object(meta_id), InstanceAttributes::Object(fields)) => match self.nested_meta_entities.get(meta_id) {
+                Some(entities) => entities
+                    .values()
+                    .try_for_each(|field| self.check_constraints(field, fields.get(field.get_name()))),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Builds and returns the final `InstanceObject`, computing, then checking every entity, reference and rule.
+    pub fn build(mut self) -> Result<InstanceObject, ObjectError> {
+        self.instance_meta_ids.get_or_insert_with(HashMap::new);
+        if let Some((_, error)) = self.recompute().into_iter().next() {
+            return Err(ObjectError::InvalidEntity(error));
+        }
+
+        let mut missing = self.missing_required(
+            &self.meta_entities,
+            &|name| self.instance_entities.get(name).map(|instance| instance.get_attribute()),
+            "",
+        );
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(ObjectError::MissingRequiredEntities(missing));
+        }
+
+        let mut entities = self.meta_entities.values().collect::<Vec<&MetaEntity>>();
+        entities.sort_by(|left, right| left.get_name().cmp(right.get_name()));
+        entities.into_iter().try_for_each(|entity| {
+            let value = self.instance_entities.get(entity.get_name()).map(|instance| instance.get_attribute());
+            self.check_constraints(entity, value)?;
+            value.map_or(Ok(()), |value| self.check_references(entity.get_attribute(), value))
+        })?;
+        self.check_rules()?;
+
+        Ok(InstanceObject::new_instance(
             &self.name,
             self.instance_entities,
             &self.meta_id,
-        )
+        )?)
     }
 }
 
@@ -98,7 +551,9 @@ impl InstanceObjectBuilder {
 #[cfg(test)]
 mod test {
    
-    use crate::core::meta::meta_entity::MetaAttributes;
+    use crate::core::errors::ExpressionError;
+    use crate::core::meta::{meta_constraint::Constraint, meta_entity::MetaAttributes};
+    use crate::core::model::Decimal;
     use crate::core::model::unique_id::Identifier;
     use super::*;
 
@@ -135,7 +590,7 @@ mod test {
 
         assert_eq!(
             output,
-            Err(AttributeError::NonMatchingType("tt".to_string()))
+            Err(AttributeError::UnknownEntity("attribute1".to_string()))
         )
     }
 
@@ -176,6 +631,99 @@ mod test {
         assert!(instance_object.id.get_id().to_string().starts_with("instance"));
     }
 
+    #[test]
+    fn test_update_nested_object_entity() {
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        address.update_entity("city", MetaAttributes::Text);
+        address.update_entity("postcode", MetaAttributes::I32);
+        let mut event = MetaObject::new_meta("Event").unwrap();
+        event.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&address);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&event, "Paris Marathon").with_meta_objects(&meta_objects).unwrap();
+        instance_builder.update_entity("address.city", Some("Paris")).unwrap();
+        assert_eq!(
+            instance_builder.update_entity("address.postcode", Some("Paris")),
+            Err(AttributeError::InvalidType("Expect i32 got Paris".to_string()))
+        );
+        assert_eq!(
+            instance_builder.update_entity("address.country", Some("France")),
+            Err(AttributeError::UnknownEntity("address.country".to_string()))
+        );
+        assert!(instance_builder.update_entity("address", Some("Paris")).is_err());
+        instance_builder.populate_missing_meta_entites();
+
+        let instance_object = instance_builder.build().unwrap();
+        let address = instance_object.entities.get("address").unwrap().get_attribute();
+        assert_eq!(address.to_string(), "{city: Paris, postcode: Null}");
+    }
+
+    #[test]
+    fn test_update_nested_object_without_meta_objects() {
+        let address = MetaObject::new_meta("Address").unwrap();
+        let mut event = MetaObject::new_meta("Event").unwrap();
+        event.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+
+        let mut instance_builder = InstanceObjectBuilder::new(&event, "Paris Marathon");
+        assert_eq!(
+            instance_builder.update_entity("address.city", Some("Paris")),
+            Err(AttributeError::UnknownMetaObject(address.get_id().to_string()))
+        );
+    }
+
+    #[test]
+    fn test_with_meta_objects_rejects_unknown_and_cyclic_objects() {
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        let mut event = MetaObject::new_meta("Event").unwrap();
+        event.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+
+        let mut meta_objects = MetaObjectCollection::new();
+        assert_eq!(
+            InstanceObjectBuilder::new(&event, "Paris Marathon").with_meta_objects(&meta_objects).err(),
+            Some(AttributeError::UnknownMetaObject(address.get_id().to_string()))
+        );
+
+        address.update_entity("event", MetaAttributes::Object(event.get_id().clone()));
+        meta_objects.insert(&address);
+        meta_objects.insert(&event);
+        assert_eq!(
+            InstanceObjectBuilder::new(&event, "Paris Marathon").with_meta_objects(&meta_objects).err(),
+            Some(AttributeError::CyclicObject(event.get_id().to_string()))
+        );
+
+        let mut comment = MetaObject::new_meta("Comment").unwrap();
+        comment.update_entity("replies", MetaAttributes::List(Box::new(MetaAttributes::Object(comment.get_id().clone()))));
+        meta_objects.insert(&comment);
+        assert!(InstanceObjectBuilder::new(&comment, "Thread").with_meta_objects(&meta_objects).is_ok());
+
+        let mut node = MetaObject::new_meta("Node").unwrap();
+        node.update_entity("next", MetaAttributes::Object(node.get_id().clone()));
+        meta_objects.insert(&node);
+        assert_eq!(
+            InstanceObjectBuilder::new(&node, "Chain").with_meta_objects(&meta_objects).err(),
+            Some(AttributeError::CyclicObject(node.get_id().to_string()))
+        );
+    }
+
+    #[test]
+    fn test_with_meta_objects_rejects_embedded_computed_entities() {
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        address.update_entity("city", MetaAttributes::Text);
+        address.update_entity("label", MetaAttributes::Text);
+        address.set_computed("label", "city + \"!\"").unwrap();
+        let mut event = MetaObject::new_meta("Event").unwrap();
+        event.update_entity("addresses", MetaAttributes::List(Box::new(MetaAttributes::Object(address.get_id().clone()))));
+
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&address);
+        assert_eq!(
+            InstanceObjectBuilder::new(&event, "Paris Marathon").with_meta_objects(&meta_objects).err(),
+            Some(AttributeError::EmbeddedComputedEntity("Address.label".to_string()))
+        );
+    }
+
     #[test]
     fn test_invalid_update_entity() {
         let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
@@ -189,4 +737,394 @@ mod test {
         let invalid_result = instance_builder.update_entity("attr2", Some("wrong_type"));
         assert!(invalid_result.is_err());
     }
+
+    #[test]
+    fn test_update_reference_entity() {
+        let organizer = MetaObject::new_meta("Organizer").unwrap();
+        let sponsor = MetaObject::new_meta("Sponsor").unwrap();
+        let mut marathon = MetaObject::new_meta("Marathon").unwrap();
+        marathon.update_entity("organizer", MetaAttributes::Reference(organizer.get_id().clone()));
+
+        let paris = InstanceObjectBuilder::new(&organizer, "Paris").build().unwrap();
+        let brand = InstanceObjectBuilder::new(&sponsor, "Brand").build().unwrap();
+        let mut instance_objects = InstanceObjectCollection::new();
+        instance_objects.insert(&paris);
+        instance_objects.insert(&brand);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&marathon, "Paris Marathon").with_instance_objects(&instance_objects);
+        instance_builder.update_entity("organizer", Some(&paris.get_id().to_string())).unwrap();
+        assert_eq!(
+            instance_builder.update_entity("organizer", Some(&brand.get_id().to_string())),
+            Err(AttributeError::InvalidReference {
+                id: brand.get_id().to_string(),
+                meta: organizer.get_id().to_string()
+            })
+        );
+
+        let instance_object = instance_builder.build().unwrap();
+        assert_eq!(
+            instance_object.entities.get("organizer").unwrap().get_attribute(),
+            &InstanceAttributes::Reference(Some(paris.get_id().clone()))
+        );
+
+        let mut instance_builder = InstanceObjectBuilder::new(&marathon, "Berlin Marathon");
+        instance_builder.update_entity("organizer", Some(&paris.get_id().to_string())).unwrap();
+        assert!(matches!(
+            instance_builder.build(),
+            Err(ObjectError::InvalidEntity(AttributeError::InvalidReference { id, .. })) if id == paris.get_id().to_string()
+        ));
+
+        let mut instance_builder = InstanceObjectBuilder::new(&marathon, "Lyon Marathon");
+        instance_builder.update_entity("organizer", Some(&brand.get_id().to_string())).unwrap();
+        assert!(matches!(
+            instance_builder.with_instance_objects(&instance_objects).build(),
+            Err(ObjectError::InvalidEntity(AttributeError::InvalidReference { id, .. })) if id == brand.get_id().to_string()
+        ));
+    }
+
+    #[test]
+    fn test_update_entity_with_constraints() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.insert_entity(
+            MetaEntity::new("prize", MetaAttributes::I32)
+                .with_constraint(Constraint::Min(Decimal::new(0, 0)))
+                .unwrap(),
+        );
+        meta_object.insert_entity(
+            MetaEntity::new("country", MetaAttributes::Text)
+                .with_constraint(Constraint::Required)
+                .and_then(|entity| entity.with_constraint(Constraint::pattern("[A-Z]{2}")?))
+                .unwrap(),
+        );
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        assert_eq!(
+            instance_builder.update_entity("prize", Some("-5")),
+            Err(AttributeError::ConstraintViolation {
+                entity: "prize".to_string(),
+                rule: "min 0".to_string(),
+                value: "-5".to_string()
+            })
+        );
+        assert!(matches!(
+            instance_builder.update_entity("country", Some("France")),
+            Err(AttributeError::ConstraintViolation { rule, .. }) if rule == "pattern ^(?:[A-Z]{2})$"
+        ));
+        assert!(instance_builder.update_entity("prize", Some("45000")).is_ok());
+
+        assert!(matches!(
+            instance_builder.build(),
+            Err(ObjectError::MissingRequiredEntities(missing)) if missing == vec!["country".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_build_reports_missing_required_entities() {
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        address.update_entity("city", MetaAttributes::Text);
+        address.update_entity("postcode", MetaAttributes::I32);
+        address.require("city").unwrap();
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I32);
+        meta_object.update_entity("distance", MetaAttributes::F64);
+        meta_object.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+        meta_object.require("name").unwrap();
+        meta_object.require("prize").unwrap();
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&address);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon").with_meta_objects(&meta_objects).unwrap();
+        instance_builder.populate_missing_meta_entites();
+        assert!(matches!(
+            instance_builder.build(),
+            Err(ObjectError::MissingRequiredEntities(missing)) if missing == vec!["name".to_string(), "prize".to_string()]
+        ));
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon").with_meta_objects(&meta_objects).unwrap();
+        instance_builder.update_entity("name", Some("Paris Marathon")).unwrap();
+        instance_builder.update_entity("address.postcode", Some("75016")).unwrap();
+        instance_builder.populate_missing_meta_entites();
+        assert!(!instance_builder.instance_entities.contains_key("prize"));
+        assert!(instance_builder.instance_entities.contains_key("distance"));
+        assert!(matches!(
+            instance_builder.build(),
+            Err(ObjectError::MissingRequiredEntities(missing)) if missing == vec!["address.city".to_string(), "prize".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_build_reports_empty_required_collections() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("tags", MetaAttributes::Set(Box::new(MetaAttributes::Text)));
+        meta_object.update_entity("speakers", MetaAttributes::List(Box::new(MetaAttributes::Text)));
+        meta_object.require("tags").unwrap();
+        meta_object.require("speakers").unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        assert!(matches!(
+            instance_builder.update_entity("tags", Some(" ")),
+            Err(AttributeError::ConstraintViolation { rule, .. }) if rule == "required"
+        ));
+        instance_builder.update_entity("tags", Some("road, city")).unwrap();
+        instance_builder
+            .instance_entities
+            .insert("speakers".to_string(), InstanceEntity::new("speakers", InstanceAttributes::List(Vec::new())));
+        assert!(matches!(
+            instance_builder.build(),
+            Err(ObjectError::MissingRequiredEntities(missing)) if missing == vec!["speakers".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_populate_missing_entities_with_defaults() {
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        address.update_entity("country", MetaAttributes::Text);
+        address.set_default("country", "FR").unwrap();
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("currency", MetaAttributes::Text);
+        meta_object.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+        meta_object.require("currency").unwrap();
+        meta_object.set_default("currency", "EUR").unwrap();
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&address);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon").with_meta_objects(&meta_objects).unwrap();
+        instance_builder.populate_missing_meta_entites();
+        let instance_object = instance_builder.build().unwrap();
+
+        let value = |name: &str| instance_object.entities.get(name).unwrap().get_attribute().to_string();
+        assert_eq!(value("currency"), "EUR");
+        assert_eq!(value("address"), "{country: FR}");
+    }
+
+    #[test]
+    fn test_computed_entities() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("year", MetaAttributes::I32);
+        meta_object.update_entity("prize", MetaAttributes::I32);
+        meta_object.update_entity("distance", MetaAttributes::F64);
+        meta_object.update_entity("full_title", MetaAttributes::Text);
+        meta_object.update_entity("prize_per_km", MetaAttributes::F64);
+        meta_object.set_computed("full_title", "name + \" (\" + year + \")\"").unwrap();
+        meta_object.set_computed("prize_per_km", "prize / distance").unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        instance_builder.update_entity("name", Some("Paris Marathon")).unwrap();
+        instance_builder.update_entity("year", Some("2024")).unwrap();
+        instance_builder.update_entity("prize", Some("45000")).unwrap();
+        assert_eq!(
+            instance_builder.instance_entities.get("prize_per_km").unwrap().get_attribute(),
+            &InstanceAttributes::F64(None)
+        );
+        instance_builder.update_entity("distance", Some("42.195")).unwrap();
+
+        assert_eq!(
+            instance_builder.update_entity("full_title", Some("Marathon de Paris")),
+            Err(AttributeError::ReadOnlyEntity("full_title".to_string()))
+        );
+        assert!(matches!(
+            instance_builder.update_entity("distance", Some("0")),
+            Err(AttributeError::ComputationFailed { entity, error: ExpressionError::DivisionByZero }) if entity == "prize_per_km"
+        ));
+
+        instance_builder.populate_missing_meta_entites();
+        let instance_object = instance_builder.build().unwrap();
+        let value = |name: &str| instance_object.entities.get(name).unwrap().get_attribute().to_string();
+        assert_eq!(value("full_title"), "Paris Marathon (2024)");
+        assert_eq!(value("distance"), "42.195");
+        assert!(matches!(
+            instance_object.entities.get("prize_per_km").unwrap().get_attribute(),
+            InstanceAttributes::F64(Some(number)) if (number - 1066.477).abs() < 0.001
+        ));
+    }
+
+    #[test]
+    fn test_build_computes_entities() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("prize", MetaAttributes::I32);
+        meta_object.update_entity("bonus", MetaAttributes::I32);
+        meta_object.update_entity("total", MetaAttributes::I16);
+        meta_object.set_computed("total", "prize + bonus").unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        instance_builder.populate_missing_meta_entites();
+        let instance_object = instance_builder.build().unwrap();
+        assert_eq!(instance_object.entities.get("total").unwrap().get_attribute(), &InstanceAttributes::I16(None));
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        instance_builder.update_entity("prize", Some("45000")).unwrap();
+        assert!(instance_builder.update_entity("bonus", Some("5000")).is_err());
+        assert!(!instance_builder.instance_entities.contains_key("bonus"));
+    }
+
+    #[test]
+    fn test_rules_checked_on_update_and_build() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("start_date", MetaAttributes::Date(None));
+        meta_object.update_entity("end_date", MetaAttributes::Date(None));
+        meta_object.update_entity("status", MetaAttributes::Text);
+        meta_object.update_entity("ref_link", MetaAttributes::Url);
+        meta_object.add_rule("dates", "end_date >= start_date").unwrap();
+        meta_object.add_rule("published_link", "status != \"published\" || is_set(ref_link)").unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        instance_builder.update_entity("start_date", Some("2024-04-07")).unwrap();
+        assert_eq!(
+            instance_builder.update_entity("end_date", Some("2024-04-06")),
+            Err(AttributeError::RuleViolation {
+                rule: "dates".to_string(),
+                entities: vec!["end_date".to_string(), "start_date".to_string()]
+            })
+        );
+        assert!(!instance_builder.instance_entities.contains_key("end_date"));
+        instance_builder.update_entity("end_date", Some("2024-04-07")).unwrap();
+
+        assert!(matches!(
+            instance_builder.update_entity("status", Some("published")),
+            Err(AttributeError::RuleViolation { rule, .. }) if rule == "published_link"
+        ));
+        assert!(!instance_builder.instance_entities.contains_key("status"));
+        instance_builder.update_entity("ref_link", Some("https://www.schneiderelectricparismarathon.com")).unwrap();
+        instance_builder.update_entity("status", Some("published")).unwrap();
+        instance_builder.populate_missing_meta_entites();
+        assert!(instance_builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_rules_of_nested_objects() {
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        address.update_entity("city", MetaAttributes::Text);
+        address.update_entity("postcode", MetaAttributes::I32);
+        address.add_rule("postcode_range", "postcode < 100000").unwrap();
+        let mut event = MetaObject::new_meta("Event").unwrap();
+        event.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&address);
+        let mut instance_builder = InstanceObjectBuilder::new(&event, "Paris Marathon")
+            .with_meta_objects(&meta_objects)
+            .unwrap();
+
+        let inputs = HashMap::from([
+            ("address.city".to_string(), "Paris".to_string()),
+            ("address.postcode".to_string(), "750001".to_string()),
+        ]);
+        let report = instance_builder.update_entities(&inputs).unwrap_err();
+        assert_eq!(
+            report.get_rule_errors().get("address.postcode_range"),
+            Some(&AttributeError::RuleViolation {
+                rule: "address.postcode_range".to_string(),
+                entities: vec!["address.postcode".to_string()]
+            })
+        );
+        assert_eq!(report.get_failing_entities(), vec!["address.postcode"]);
+
+        assert!(matches!(
+            instance_builder.update_entity("address.postcode", Some("750001")),
+            Err(AttributeError::RuleViolation { rule, .. }) if rule == "address.postcode_range"
+        ));
+        assert!(!instance_builder.instance_entities.contains_key("address"));
+    }
+
+    #[test]
+    fn test_build_reports_broken_rules() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.insert_entity(MetaEntity::new("min_age", MetaAttributes::I16).with_default("18").unwrap());
+        meta_object.insert_entity(MetaEntity::new("max_age", MetaAttributes::I16).with_default("16").unwrap());
+        meta_object.add_rule("ages", "min_age < max_age").unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        instance_builder.populate_missing_meta_entites();
+        assert!(matches!(
+            instance_builder.build(),
+            Err(ObjectError::InvalidEntity(AttributeError::RuleViolation { rule, entities }))
+                if rule == "ages" && entities == vec!["max_age".to_string(), "min_age".to_string()]
+        ));
+    }
+
+    #[test]
+    fn test_update_entities_reports_every_error() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I32);
+        meta_object.update_entity("ref_link", MetaAttributes::Url);
+        meta_object.update_entity("min_age", MetaAttributes::I16);
+        meta_object.update_entity("max_age", MetaAttributes::I16);
+        meta_object.update_entity("label", MetaAttributes::Text);
+        meta_object.set_computed("label", "name + \" - \" + prize").unwrap();
+        meta_object.add_rule("ages", "min_age < max_age").unwrap();
+        let inputs = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(name, input)| (name.to_string(), input.to_string()))
+                .collect::<HashMap<String, String>>()
+        };
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        let report = instance_builder
+            .update_entities(&inputs(&[
+                ("name", "Paris Marathon"),
+                ("prize", "lots"),
+                ("ref_link", "www.example.com"),
+                ("label", "Paris"),
+                ("min_age", "18"),
+                ("max_age", "16"),
+            ]))
+            .unwrap_err();
+
+        assert_eq!(report.len(), 4);
+        assert_eq!(
+            report.get_entity_errors().keys().map(String::as_str).collect::<Vec<&str>>(),
+            vec!["label", "prize", "ref_link"]
+        );
+        assert_eq!(report.get_entity_error("label"), Some(&AttributeError::ReadOnlyEntity("label".to_string())));
+        assert_eq!(
+            report.get_entity_error("ref_link"),
+            Some(&AttributeError::InvalidUrl("www.example.com".to_string()))
+        );
+        assert!(report.get_rule_errors().contains_key("ages"));
+        assert_eq!(report.get_failing_entities(), vec!["label", "max_age", "min_age", "prize", "ref_link"]);
+        assert!(instance_builder.instance_entities.is_empty());
+
+        instance_builder
+            .update_entities(&inputs(&[("name", "Paris Marathon"), ("prize", "45000"), ("min_age", "16"), ("max_age", "18")]))
+            .unwrap();
+        instance_builder.populate_missing_meta_entites();
+        let instance_object = instance_builder.build().unwrap();
+        assert_eq!(instance_object.entities.get("label").unwrap().get_attribute().to_string(), "Paris Marathon - 45000");
+    }
+
+    #[test]
+    fn test_update_entities_reports_every_computation_and_unknown_entity() {
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("prize", MetaAttributes::I32);
+        meta_object.update_entity("distance", MetaAttributes::I32);
+        meta_object.update_entity("runners", MetaAttributes::I32);
+        meta_object.update_entity("prize_per_km", MetaAttributes::F64);
+        meta_object.update_entity("prize_per_runner", MetaAttributes::F64);
+        meta_object.set_computed("prize_per_km", "prize / distance").unwrap();
+        meta_object.set_computed("prize_per_runner", "prize / runners").unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon");
+        let inputs = HashMap::from([
+            ("prize".to_string(), "45000".to_string()),
+            ("distance".to_string(), "0".to_string()),
+            ("runners".to_string(), "0".to_string()),
+            ("city".to_string(), "Paris".to_string()),
+        ]);
+        let report = instance_builder.update_entities(&inputs).unwrap_err();
+
+        assert_eq!(
+            report.get_entity_errors().keys().map(String::as_str).collect::<Vec<&str>>(),
+            vec!["city", "prize_per_km", "prize_per_runner"]
+        );
+        assert_eq!(report.get_entity_error("city"), Some(&AttributeError::UnknownEntity("city".to_string())));
+        assert!(matches!(
+            report.get_entity_error("prize_per_runner"),
+            Some(AttributeError::ComputationFailed { error: ExpressionError::DivisionByZero, .. })
+        ));
+        assert!(instance_builder.instance_entities.is_empty());
+    }
 }
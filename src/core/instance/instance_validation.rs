@@ -0,0 +1,113 @@
+//! # ValidationReport
+//!
+//! A `ValidationReport` gathers every problem found while validating several inputs at once, so that an intake
+//! form can show all of them instead of the first one.
+//!
+//! ## Responsibilities:
+//! - Keeps the error of each rejected entity, by entity name or dotted path.
+//! - Keeps the error of each broken rule of the `MetaObject`, by rule name.
+//! - Lists the entities involved, those rejected and those read by broken rules.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::core::errors::AttributeError;
+
+#[derive(Debug, Default, PartialEq)]
+/// The errors found while validating the inputs of an instance, sorted by entity and by rule.
+pub struct ValidationReport {
+    entities: BTreeMap<String, AttributeError>,
+    rules: BTreeMap<String, AttributeError>,
+}
+
+impl ValidationReport {
+    /// Records the error of an entity, replacing the previous one.
+    pub fn add_entity_error(&mut self, entity: &str, error: AttributeError) {
+        self.entities.insert(entity.to_string(), error);
+    }
+
+    /// Records the error of a rule, replacing the previous one.
+    pub fn add_rule_error(&mut self, rule: &str, error: AttributeError) {
+        self.rules.insert(rule.to_string(), error);
+    }
+
+    /// Returns whether no error was found.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.rules.is_empty()
+    }
+
+    /// Returns the number of errors, of entities and rules.
+    pub fn len(&self) -> usize {
+        self.entities.len() + self.rules.len()
+    }
+
+    /// Returns the error of an entity, if it was rejected.
+    pub fn get_entity_error(&self, entity: &str) -> Option<&AttributeError> {
+        self.entities.get(entity)
+    }
+
+    /// Returns the errors of the rejected entities, sorted by entity.
+    pub fn get_entity_errors(&self) -> &BTreeMap<String, AttributeError> {
+        &self.entities
+    }
+
+    /// Returns the errors of the broken rules, sorted by rule.
+    pub fn get_rule_errors(&self) -> &BTreeMap<String, AttributeError> {
+        &self.rules
+    }
+
+    /// Returns the rejected entities and the entities read by broken rules, sorted and without duplicates.
+    pub fn get_failing_entities(&self) -> Vec<&str> {
+        let mut failing = self.entities.keys().map(String::as_str).collect::<Vec<&str>>();
+        self.rules.values().for_each(|error| {
+            if let AttributeError::RuleViolation { entities, .. } = error {
+                failing.extend(entities.iter().map(String::as_str));
+            }
+        });
+        failing.sort();
+        failing.dedup();
+        failing
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ValidationReport: {} error(s)", self.len())?;
+        self.entities
+            .iter()
+            .chain(self.rules.iter())
+            .try_for_each(|(name, error)| write!(f, "\n- {}: {}", name, error))
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validation_report() {
+        let mut report = ValidationReport::default();
+        assert!(report.is_empty());
+
+        report.add_entity_error("prize", AttributeError::InvalidType("Expect i32 got abc".to_string()));
+        report.add_rule_error(
+            "ages",
+            AttributeError::RuleViolation {
+                rule: "ages".to_string(),
+                entities: vec!["max_age".to_string(), "prize".to_string()],
+            },
+        );
+
+        assert_eq!(report.len(), 2);
+        assert!(report.get_entity_error("prize").is_some());
+        assert!(report.get_entity_error("max_age").is_none());
+        assert_eq!(report.get_failing_entities(), vec!["max_age", "prize"]);
+        assert_eq!(
+            report.to_string(),
+            "ValidationReport: 2 error(s)\n\
+             - prize: AttributeError: Invalid Type Expect i32 got abc\n\
+             - ages: AttributeError: Rule ages is broken by entities [\"max_age\", \"prize\"]"
+        );
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! `InstanceAttributes` represent the actual values of attributes for specific entities
 //! in an instance object, matching the schema defined by `MetaAttributes`.
+//! A `ValidationReport` gathers every error found when several inputs are validated at once.
 
 pub mod instance_entities;
-pub mod instance_object;
\ No newline at end of file
+pub mod instance_object;
+pub mod instance_validation;
\ No newline at end of file
@@ -0,0 +1,161 @@
+//! # Constraint
+//!
+//! A `Constraint` is a validation rule declared on a `MetaEntity`, on top of its type.
+//!
+//! ## Rules:
+//! - `Required`: the value must be set. `List` and `Set` values must hold an element, and `Object` values must
+//!   have one of their fields set.
+//! - `Min` and `Max`: inclusive bounds of integer, `F64`, `Decimal` and `Money` values.
+//! - `MinLength` and `MaxLength`: inclusive bounds of the number of characters of `Text`, `Url`, `Email`,
+//!   `Phone` and `Slug` values.
+//! - `Pattern`: a regular expression the whole `Text`, `Url`, `Email`, `Phone` or `Slug` value must match.
+//!
+//! Missing values only break `Required`, the other rules applying to set values.
+
+use std::{cmp::Ordering, fmt};
+
+use regex::Regex;
+
+use crate::core::{errors::AttributeError, instance::instance_entities::InstanceAttributes, model::Decimal};
+
+use super::meta_entity::MetaAttributes;
+
+#[derive(Debug, Clone)]
+/// A validation rule on the value of an entity.
+pub enum Constraint {
+    Required,
+    Min(Decimal),
+    Max(Decimal),
+    MinLength(usize),
+    MaxLength(usize),
+    Pattern(Regex),
+}
+
+impl Constraint {
+    /// Creates a `Pattern` constraint, the pattern being anchored so that it must match the whole value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidPattern` if the pattern is not a valid regular expression.
+    pub fn pattern(pattern: &str) -> Result<Constraint, AttributeError> {
+        Regex::new(&format!("^(?:{pattern})$"))
+            .map(Constraint::Pattern)
+            .map_err(|_| AttributeError::InvalidPattern(pattern.to_string()))
+    }
+
+    /// Returns whether the rule can be declared on an entity of the given type.
+    pub fn applies_to(&self, attribute: &MetaAttributes) -> bool {
+        match &self {
+            Constraint::Required => true,
+            Constraint::Min(_) | Constraint::Max(_) => attribute.is_numeric(),
+            Constraint::MinLength(_) | Constraint::MaxLength(_) | Constraint::Pattern(_) => attribute.is_textual(),
+        }
+    }
+
+    /// Returns whether the value follows the rule.
+    pub fn is_satisfied_by(&self, value: &InstanceAttributes) -> bool {
+        if value.is_null() {
+            return !matches!(self, Constraint::Required);
+        }
+
+        match (&self, value.as_text()) {
+            (Constraint::Required, _) => match value {
+                InstanceAttributes::List(_) | InstanceAttributes::Set(_) | InstanceAttributes::Object(_) => value.is_truthy(),
+                _ => true,
+            },
+            (Constraint::Min(bound), _) => Self::compare(value, bound).is_none_or(|order| order != Ordering::Less),
+            (Constraint::Max(bound), _) => Self::compare(value, bound).is_none_or(|order| order != Ordering::Greater),
+            (Constraint::MinLength(length), Some(text)) => text.chars().count() >= *length,
+            (Constraint::MaxLength(length), Some(text)) => text.chars().count() <= *length,
+            (Constraint::Pattern(regex), Some(text)) => regex.is_match(text),
+            (_, None) => true,
+        }
+    }
+
+    /// Compares a numeric value with a bound, `None` for values that are not numbers.
+    fn compare(value: &InstanceAttributes, bound: &Decimal) -> Option<Ordering> {
+        match (value, value.as_integer()) {
+            (_, Some(integer)) => Some(Decimal::new(integer, 0).cmp_value(bound)),
+            (InstanceAttributes::F64(Some(number)), _) => number.partial_cmp(&bound.to_f64()),
+            (InstanceAttributes::Decimal(Some(number)), _) => Some(number.cmp_value(bound)),
+            (InstanceAttributes::Money(Some(money)), _) => Some(money.get_amount().cmp_value(bound)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self {
+            Constraint::Required => write!(f, "required"),
+            Constraint::Min(bound) => write!(f, "min {}", bound),
+            Constraint::Max(bound) => write!(f, "max {}", bound),
+            Constraint::MinLength(length) => write!(f, "min length {}", length),
+            Constraint::MaxLength(length) => write!(f, "max length {}", length),
+            Constraint::Pattern(regex) => write!(f, "pattern {}", regex.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn text(value: &str) -> InstanceAttributes {
+        InstanceAttributes::Text(Some(value.to_string()))
+    }
+
+    #[test]
+    fn test_required() {
+        assert!(!Constraint::Required.is_satisfied_by(&InstanceAttributes::I32(None)));
+        assert!(Constraint::Required.is_satisfied_by(&InstanceAttributes::I32(Some(0))));
+        assert!(Constraint::MinLength(3).is_satisfied_by(&InstanceAttributes::Text(None)));
+        assert!(!Constraint::Required.is_satisfied_by(&InstanceAttributes::List(Vec::new())));
+        assert!(Constraint::Required.is_satisfied_by(&InstanceAttributes::Set(vec![text("FR")])));
+        assert!(!Constraint::Required.is_satisfied_by(&InstanceAttributes::Object(BTreeMap::from([(
+            "city".to_string(),
+            InstanceAttributes::Text(None)
+        )]))));
+        assert!(Constraint::Required.is_satisfied_by(&InstanceAttributes::Object(BTreeMap::from([(
+            "city".to_string(),
+            text("Paris")
+        )]))));
+    }
+
+    #[test]
+    fn test_numeric_bounds() {
+        let min = Constraint::Min(Decimal::new(0, 0));
+        let max = Constraint::Max(Decimal::new(42195, 3));
+        assert!(min.is_satisfied_by(&InstanceAttributes::I64(Some(0))));
+        assert!(!min.is_satisfied_by(&InstanceAttributes::I16(Some(-1))));
+        assert!(max.is_satisfied_by(&InstanceAttributes::F64(Some(42.195))));
+        assert!(!max.is_satisfied_by(&InstanceAttributes::Decimal(Some(Decimal::new(42196, 3)))));
+        assert!(max.is_satisfied_by(&InstanceAttributes::U64(Some(42))));
+        assert!(!max.is_satisfied_by(&InstanceAttributes::U64(Some(u64::MAX))));
+        assert_eq!(max.to_string(), "max 42.195");
+    }
+
+    #[test]
+    fn test_text_rules() {
+        assert!(Constraint::MinLength(2).is_satisfied_by(&text("FR")));
+        assert!(!Constraint::MaxLength(2).is_satisfied_by(&text("FRA")));
+        assert!(Constraint::MaxLength(3).is_satisfied_by(&text("été")));
+
+        let pattern = Constraint::pattern("[A-Z]{2}").unwrap();
+        assert!(pattern.is_satisfied_by(&text("FR")));
+        assert!(!pattern.is_satisfied_by(&text("FRA")));
+        assert_eq!(pattern.to_string(), "pattern ^(?:[A-Z]{2})$");
+        assert!(matches!(Constraint::pattern("[A-Z"), Err(AttributeError::InvalidPattern(_))));
+    }
+
+    #[test]
+    fn test_applies_to() {
+        assert!(Constraint::Min(Decimal::new(1, 0)).applies_to(&MetaAttributes::I32));
+        assert!(!Constraint::Min(Decimal::new(1, 0)).applies_to(&MetaAttributes::Text));
+        assert!(Constraint::MaxLength(20).applies_to(&MetaAttributes::Slug));
+        assert!(!Constraint::MaxLength(20).applies_to(&MetaAttributes::List(Box::new(MetaAttributes::Text))));
+        assert!(Constraint::Required.applies_to(&MetaAttributes::Duration));
+    }
+}
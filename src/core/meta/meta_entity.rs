@@ -1,23 +1,214 @@
-use crate::core::{instance::instance_entities::InstanceAttributes, model::entity::Entity,errors::AttributeError};
+use std::collections::BTreeMap;
 
+use crate::core::{expression::expression_node::Expression, instance::instance_entities::InstanceAttributes, model::{entity::EntityTraits, Currency, UniqueId},errors::AttributeError};
 
-
-pub type MetaEntity = Entity<MetaAttributes>;
+use super::meta_constraint::Constraint;
 
 
 #[derive(Debug, Clone)]
+/// An entity of a `MetaObject`: a name, the type of its values, the constraints they must follow,
+/// an optional default value and, for computed entities, the expression their value is computed with.
+pub struct MetaEntity {
+    name: String,
+    attribute: MetaAttributes,
+    constraints: Vec<Constraint>,
+    default: Option<InstanceAttributes>,
+    expression: Option<Expression>,
+}
+
+impl EntityTraits<MetaAttributes> for MetaEntity {
+    /// Creates an entity without constraints.
+    fn new(name: &str, attribute: MetaAttributes) -> Self {
+        MetaEntity {
+            name: name.to_string(),
+            attribute,
+            constraints: Vec::new(),
+            default: None,
+            expression: None,
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_attribute(&self) -> &MetaAttributes {
+        &self.attribute
+    }
+}
+
+impl MetaEntity {
+    /// Adds a constraint the values of the entity must follow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidConstraint` if the constraint does not apply to the type of the entity,
+    /// or an `AttributeError::ConstraintViolation` if the default value of the entity breaks it.
+    pub fn with_constraint(mut self, constraint: Constraint) -> Result<Self, AttributeError> {
+        if !constraint.applies_to(&self.attribute) {
+            return Err(AttributeError::InvalidConstraint {
+                entity: self.name,
+                rule: constraint.to_string(),
+            });
+        }
+        self.constraints.push(constraint);
+        match &self.default {
+            Some(default) => self.check(default).map(|_| self),
+            None => Ok(self),
+        }
+    }
+
+    /// Sets the value given to the entity when an instance leaves it missing, parsed as the type of the entity,
+    /// e.g. `EUR` for a `Text` currency code.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `AttributeError` of the parsing if the input is not a value of the type of the entity,
+    /// or an `AttributeError::ConstraintViolation` if it breaks one of the constraints of the entity.
+    pub fn with_default(mut self, input: &str) -> Result<Self, AttributeError> {
+        let default = self.attribute.parse_attribute(Some(input))?;
+        self.check(&default)?;
+        self.default = Some(default);
+        Ok(self)
+    }
+
+    /// Returns the default value of the entity, if it has one.
+    pub fn get_default(&self) -> Option<&InstanceAttributes> {
+        self.default.as_ref()
+    }
+
+    /// Makes the entity computed with an expression, checked beforehand by `MetaObject::set_computed`.
+    pub(crate) fn with_expression(mut self, expression: Expression) -> Self {
+        self.expression = Some(expression);
+        self
+    }
+
+    /// Returns the expression the value of the entity is computed with, if it is computed.
+    pub fn get_expression(&self) -> Option<&Expression> {
+        self.expression.as_ref()
+    }
+
+    /// Returns whether the value of the entity is computed rather than given.
+    pub fn is_computed(&self) -> bool {
+        self.expression.is_some()
+    }
+
+    /// Returns whether the entity must be given a value, i.e. has a `Required` constraint.
+    pub fn is_required(&self) -> bool {
+        self.constraints.iter().any(|constraint| matches!(constraint, Constraint::Required))
+    }
+
+    /// Returns the constraints of the entity, in the order they were declared.
+    pub fn get_constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Checks a value against the constraints of the entity, in the order they were declared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::ConstraintViolation` naming the entity and the first rule the value breaks.
+    pub fn check(&self, value: &InstanceAttributes) -> Result<(), AttributeError> {
+        match self.constraints.iter().find(|constraint| !constraint.is_satisfied_by(value)) {
+            Some(constraint) => Err(AttributeError::ConstraintViolation {
+                entity: self.name.to_string(),
+                rule: constraint.to_string(),
+                value: value.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
 // Enum representing various types of meta attributes.
 /// 
-/// These define the data types for attributes, such as `Text` or `I16` (16-bit integer),
-/// that can be later instantiated with actual values in instance objects.
+/// These define the data types for attributes, such as `Text`, `Bool`, integers from `I16` to `U64`
+/// or `F64` floating point numbers, that can be later instantiated with actual values in instance objects.
+///
+/// `Decimal` holds exact numbers with at most the given number of fractional digits, up to `Decimal::MAX_SCALE`
+/// as checked by `MetaObject::add_entity`, and `Money` exact amounts of the given currency.
+///
+/// `Date`, `DateTime` and `Time` take an optional `strftime` input format, ISO 8601 being used by default:
+/// `%Y-%m-%d` for dates, RFC 3339 for date-times, which carry their timezone offset, and `%H:%M:%S` or `%H:%M`
+/// for times. `Duration` reads ISO 8601 durations (`PT2H5M`) or clock times (`2:05:00`).
+///
+/// `Url` accepts absolute `http` and `https` URLs, `Email` email addresses, `Phone` E.164 numbers (`+33123456789`,
+/// spaces and dashes being dropped) and `Slug` lowercase words separated by dashes (`paris-2024`).
+///
+/// `Enum` only accepts the keys of its choices.
+///
+/// `Object` embeds the entities of another `MetaObject`, given by id. Its values are set one entity at a time,
+/// through dotted paths such as `address.city`.
+///
+/// `Reference` points at another instance of the `MetaObject` given by id, its value being the id of that instance.
+///
+/// `List` and `Set` hold several values of their element type, a `Set` dropping duplicates.
 pub enum MetaAttributes {
     Text,
+    Bool,
     I16,
+    I32,
+    I64,
+    U64,
+    F64,
+    Decimal(u32),
+    Money(Currency),
+    Date(Option<String>),
+    DateTime(Option<String>),
+    Time(Option<String>),
+    Duration,
+    Url,
+    Email,
+    Phone,
+    Slug,
+    Enum(Vec<EnumChoice>),
+    Object(UniqueId),
+    Reference(UniqueId),
+    List(Box<MetaAttributes>),
+    Set(Box<MetaAttributes>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// One of the allowed values of an `Enum` attribute: a key, and an optional label templates can render instead.
+pub struct EnumChoice {
+    key: String,
+    label: Option<String>,
+}
+
+impl EnumChoice {
+    /// Creates a choice without label.
+    pub fn new(key: &str) -> Self {
+        EnumChoice {
+            key: key.to_string(),
+            label: None,
+        }
+    }
+
+    /// Sets the label rendered by the `label` template filter.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Returns the key of the choice, the value given as input.
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the label of the choice, or its key when it has none.
+    pub fn get_label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.key)
+    }
 }
 
 impl MetaAttributes {
    
     /// Parses a raw string input into the corresponding `InstanceAttributes` type.
+    ///
+    /// `List` and `Set` inputs are comma-separated, each element being parsed as the element type.
+    /// Commas inside elements are escaped as `\,`.
     /// 
     /// # Arguments
     /// 
@@ -29,20 +220,118 @@ impl MetaAttributes {
     pub fn parse_attribute(&self, input:Option<&str>)->Result<InstanceAttributes,AttributeError>{
         match &self{
             MetaAttributes::Text => InstanceAttributes::parse_text(input),
+            MetaAttributes::Bool => InstanceAttributes::parse_bool(input),
             MetaAttributes::I16 =>InstanceAttributes::parse_i16(input),
+            MetaAttributes::I32 => InstanceAttributes::parse_i32(input),
+            MetaAttributes::I64 => InstanceAttributes::parse_i64(input),
+            MetaAttributes::U64 => InstanceAttributes::parse_u64(input),
+            MetaAttributes::F64 => InstanceAttributes::parse_f64(input),
+            MetaAttributes::Decimal(scale) => InstanceAttributes::parse_decimal(input, *scale),
+            MetaAttributes::Money(currency) => InstanceAttributes::parse_money(input, *currency),
+            MetaAttributes::Date(format) => InstanceAttributes::parse_date(input, format.as_deref()),
+            MetaAttributes::DateTime(format) => InstanceAttributes::parse_datetime(input, format.as_deref()),
+            MetaAttributes::Time(format) => InstanceAttributes::parse_time(input, format.as_deref()),
+            MetaAttributes::Duration => InstanceAttributes::parse_duration(input),
+            MetaAttributes::Url => InstanceAttributes::parse_url(input),
+            MetaAttributes::Email => InstanceAttributes::parse_email(input),
+            MetaAttributes::Phone => InstanceAttributes::parse_phone(input),
+            MetaAttributes::Slug => InstanceAttributes::parse_slug(input),
+            MetaAttributes::Enum(choices) => InstanceAttributes::parse_enum(input, choices),
+            MetaAttributes::Object(_) => match input {
+                Some(object) => Err(AttributeError::InvalidType(format!("Expect a dotted path to set object got {}", object))),
+                None => Ok(self.insert_none_for_type()),
+            },
+            MetaAttributes::Reference(_) => InstanceAttributes::parse_reference(input),
+            MetaAttributes::List(element) => InstanceAttributes::parse_list(element, input),
+            MetaAttributes::Set(element) => InstanceAttributes::parse_set(element, input),
         }
 
     }
 
+    /// Returns the type of the elements when the attribute is a collection, which templates can loop over.
+    pub fn get_element_type(&self) -> Option<&MetaAttributes> {
+        match &self {
+            MetaAttributes::List(element) | MetaAttributes::Set(element) => Some(element),
+            MetaAttributes::Text
+            | MetaAttributes::Bool
+            | MetaAttributes::I16
+            | MetaAttributes::I32
+            | MetaAttributes::I64
+            | MetaAttributes::U64
+            | MetaAttributes::F64
+            | MetaAttributes::Decimal(_)
+            | MetaAttributes::Money(_)
+            | MetaAttributes::Date(_)
+            | MetaAttributes::DateTime(_)
+            | MetaAttributes::Time(_)
+            | MetaAttributes::Duration
+            | MetaAttributes::Url
+            | MetaAttributes::Email
+            | MetaAttributes::Phone
+            | MetaAttributes::Slug
+            | MetaAttributes::Enum(_)
+            | MetaAttributes::Object(_)
+            | MetaAttributes::Reference(_) => None,
+        }
+    }
+
+    /// Returns whether the attribute is a `Date`, a `DateTime` or a `Time`, which `strftime` formats apply to.
+    pub fn is_temporal(&self) -> bool {
+        matches!(self, MetaAttributes::Date(_) | MetaAttributes::DateTime(_) | MetaAttributes::Time(_))
+    }
+
+    /// Returns whether the attribute is one of the integer types.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, MetaAttributes::I16 | MetaAttributes::I32 | MetaAttributes::I64 | MetaAttributes::U64)
+    }
+
+    /// Returns whether the attribute is an integer, a `F64`, a `Decimal` or a `Money`, which `min` and `max` apply to.
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || matches!(self, MetaAttributes::F64 | MetaAttributes::Decimal(_) | MetaAttributes::Money(_))
+    }
+
+    /// Returns whether the values of the attribute are strings: `Text`, `Url`, `Email`, `Phone` or `Slug`.
+    pub fn is_textual(&self) -> bool {
+        matches!(
+            self,
+            MetaAttributes::Text | MetaAttributes::Url | MetaAttributes::Email | MetaAttributes::Phone | MetaAttributes::Slug
+        )
+    }
+
+    /// Returns whether the attribute holds several values.
+    pub fn is_collection(&self) -> bool {
+        self.get_element_type().is_some()
+    }
+
     /// Provides a default `InstanceAttributes` value for the given `MetaAttributes` type.
     /// 
     /// # Returns
     /// 
-    /// An `InstanceAttributes` value with a `None` value appropriate for the type.
+    /// An `InstanceAttributes` value with a `None` value appropriate for the type, or an empty collection or object.
     pub fn insert_none_for_type(&self)->InstanceAttributes{
         match &self{
             MetaAttributes::Text => InstanceAttributes::Text(None),
+            MetaAttributes::Bool => InstanceAttributes::Bool(None),
             MetaAttributes::I16 => InstanceAttributes::I16(None),
+            MetaAttributes::I32 => InstanceAttributes::I32(None),
+            MetaAttributes::I64 => InstanceAttributes::I64(None),
+            MetaAttributes::U64 => InstanceAttributes::U64(None),
+            MetaAttributes::F64 => InstanceAttributes::F64(None),
+            MetaAttributes::Decimal(_) => InstanceAttributes::Decimal(None),
+            MetaAttributes::Money(_) => InstanceAttributes::Money(None),
+            MetaAttributes::Date(_) => InstanceAttributes::Date(None),
+            MetaAttributes::DateTime(_) => InstanceAttributes::DateTime(None),
+            MetaAttributes::Time(_) => InstanceAttributes::Time(None),
+            MetaAttributes::Duration => InstanceAttributes::Duration(None),
+            MetaAttributes::Url => InstanceAttributes::Url(None),
+            MetaAttributes::Email => InstanceAttributes::Email(None),
+            MetaAttributes::Phone => InstanceAttributes::Phone(None),
+            MetaAttributes::Slug => InstanceAttributes::Slug(None),
+            MetaAttributes::Enum(_) => InstanceAttributes::Enum(None),
+            MetaAttributes::Object(_) => InstanceAttributes::Object(BTreeMap::new()),
+            MetaAttributes::Reference(_) => InstanceAttributes::Reference(None),
+            MetaAttributes::List(_) => InstanceAttributes::List(Vec::new()),
+            MetaAttributes::Set(_) => InstanceAttributes::Set(Vec::new()),
         }
     }
 }
@@ -52,6 +341,7 @@ impl MetaAttributes {
 #[cfg(test)]
 mod test {
     use crate::core::errors::AttributeError;
+    use crate::core::model::Decimal;
 
     use super::*;
     
@@ -87,6 +377,246 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_scalars_are_not_collections() {
+        assert!(!MetaAttributes::Text.is_collection());
+        assert!(MetaAttributes::I16.get_element_type().is_none());
+        assert!(MetaAttributes::List(Box::new(MetaAttributes::Text)).is_collection());
+        assert!(matches!(
+            MetaAttributes::Set(Box::new(MetaAttributes::I16)).get_element_type(),
+            Some(MetaAttributes::I16)
+        ));
+    }
+
+    #[test]
+    fn test_parse_attribute_list_success() {
+        let meta_attr = MetaAttributes::List(Box::new(MetaAttributes::I16));
+        let result = meta_attr.parse_attribute(Some("3, 1,3"));
+        assert_eq!(
+            result,
+            Ok(InstanceAttributes::List(vec![
+                InstanceAttributes::I16(Some(3)),
+                InstanceAttributes::I16(Some(1)),
+                InstanceAttributes::I16(Some(3)),
+            ]))
+        );
+        assert_eq!(meta_attr.parse_attribute(Some(" ")), Ok(InstanceAttributes::List(Vec::new())));
+        assert_eq!(meta_attr.parse_attribute(None), Ok(InstanceAttributes::List(Vec::new())));
+    }
+
+    #[test]
+    fn test_parse_attribute_list_escaped_commas() {
+        let meta_attr = MetaAttributes::List(Box::new(MetaAttributes::Text));
+        assert_eq!(
+            meta_attr.parse_attribute(Some(r"Paris\, France, Lyon, C:\dir\\, end\")),
+            Ok(InstanceAttributes::List(vec![
+                InstanceAttributes::Text(Some("Paris, France".to_string())),
+                InstanceAttributes::Text(Some("Lyon".to_string())),
+                InstanceAttributes::Text(Some(r"C:\dir\".to_string())),
+                InstanceAttributes::Text(Some(r"end\".to_string())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_attribute_set_drops_duplicates() {
+        let meta_attr = MetaAttributes::Set(Box::new(MetaAttributes::Text));
+        let result = meta_attr.parse_attribute(Some("b, a, b"));
+        assert_eq!(
+            result,
+            Ok(InstanceAttributes::Set(vec![
+                InstanceAttributes::Text(Some("b".to_string())),
+                InstanceAttributes::Text(Some("a".to_string())),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parse_attribute_list_invalid_element() {
+        let meta_attr = MetaAttributes::List(Box::new(MetaAttributes::I16));
+        assert_eq!(
+            meta_attr.parse_attribute(Some("1, two")),
+            Err(AttributeError::InvalidElement {
+                index: 1,
+                error: Box::new(AttributeError::InvalidType("Expect i16 got two".to_string()))
+            })
+        );
+        assert!(matches!(
+            meta_attr.parse_attribute(Some("1,,2")),
+            Err(AttributeError::InvalidElement { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_attribute_numeric_family() {
+        assert_eq!(MetaAttributes::Bool.parse_attribute(Some("True")), Ok(InstanceAttributes::Bool(Some(true))));
+        assert_eq!(MetaAttributes::I32.parse_attribute(Some("40000")), Ok(InstanceAttributes::I32(Some(40000))));
+        assert_eq!(
+            MetaAttributes::I64.parse_attribute(Some("-5000000000")),
+            Ok(InstanceAttributes::I64(Some(-5_000_000_000)))
+        );
+        assert_eq!(
+            MetaAttributes::U64.parse_attribute(Some("18446744073709551615")),
+            Ok(InstanceAttributes::U64(Some(u64::MAX)))
+        );
+        assert_eq!(MetaAttributes::F64.parse_attribute(Some("42.195")), Ok(InstanceAttributes::F64(Some(42.195))));
+        assert_eq!(
+            MetaAttributes::U64.parse_attribute(Some("-1")),
+            Err(AttributeError::InvalidType("Expect u64 got -1".to_string()))
+        );
+        assert!(MetaAttributes::I16.parse_attribute(Some("40000")).is_err());
+        assert!(MetaAttributes::F64.parse_attribute(Some("NaN")).is_err());
+        assert!(MetaAttributes::Bool.parse_attribute(Some("maybe")).is_err());
+    }
+
+    #[test]
+    fn test_insert_none_for_type_numeric_family() {
+        assert_eq!(MetaAttributes::Bool.insert_none_for_type(), InstanceAttributes::Bool(None));
+        assert_eq!(MetaAttributes::I32.insert_none_for_type(), InstanceAttributes::I32(None));
+        assert_eq!(MetaAttributes::I64.insert_none_for_type(), InstanceAttributes::I64(None));
+        assert_eq!(MetaAttributes::U64.insert_none_for_type(), InstanceAttributes::U64(None));
+        assert_eq!(MetaAttributes::F64.insert_none_for_type(), InstanceAttributes::F64(None));
+    }
+
+    #[test]
+    fn test_parse_attribute_decimal_and_money() {
+        assert_eq!(
+            MetaAttributes::Decimal(2).parse_attribute(Some("12.5")),
+            Ok(InstanceAttributes::Decimal(Some(Decimal::new(1250, 2))))
+        );
+        assert_eq!(
+            MetaAttributes::Decimal(2).parse_attribute(Some("12.505")),
+            Err(AttributeError::ScaleMismatch { expected: 2, found: 3 })
+        );
+
+        let eur = Currency::from_code("EUR").unwrap();
+        let prize = MetaAttributes::Money(eur).parse_attribute(Some("2030 EUR")).unwrap();
+        assert_eq!(prize.to_string(), "2030.00 EUR");
+        assert!(matches!(
+            MetaAttributes::Money(eur).parse_attribute(Some("2030 USD")),
+            Err(AttributeError::CurrencyMismatch { .. })
+        ));
+        assert_eq!(MetaAttributes::Money(eur).insert_none_for_type(), InstanceAttributes::Money(None));
+    }
+
+    #[test]
+    fn test_parse_attribute_dates_and_times() {
+        let start = MetaAttributes::Date(None).parse_attribute(Some("2024-04-07")).unwrap();
+        assert_eq!(start.to_string(), "2024-04-07");
+        let start = MetaAttributes::Date(Some("%d/%m/%Y".to_string())).parse_attribute(Some("07/04/2024")).unwrap();
+        assert_eq!(start.to_string(), "2024-04-07");
+        assert!(MetaAttributes::Date(None).parse_attribute(Some("07/04/2024")).is_err());
+
+        let start = MetaAttributes::DateTime(None).parse_attribute(Some("2024-04-07T08:15:00+02:00")).unwrap();
+        assert_eq!(start.to_string(), "2024-04-07T08:15:00+02:00");
+        let format = Some("%d/%m/%Y %H:%M %z".to_string());
+        let start = MetaAttributes::DateTime(format).parse_attribute(Some("07/04/2024 08:15 +0200")).unwrap();
+        assert_eq!(start.to_string(), "2024-04-07T08:15:00+02:00");
+        assert!(MetaAttributes::DateTime(None).parse_attribute(Some("2024-04-07T08:15:00")).is_err());
+
+        assert_eq!(MetaAttributes::Time(None).parse_attribute(Some("08:15")).unwrap().to_string(), "08:15:00");
+        assert!(MetaAttributes::Time(None).parse_attribute(Some("25:00")).is_err());
+
+        let finish = MetaAttributes::Duration.parse_attribute(Some("PT2H5M30S")).unwrap();
+        assert_eq!(finish.to_string(), "2:05:30");
+        assert_eq!(MetaAttributes::Duration.insert_none_for_type(), InstanceAttributes::Duration(None));
+    }
+
+    #[test]
+    fn test_parse_attribute_enum() {
+        let meta_attr = MetaAttributes::Enum(vec![
+            EnumChoice::new("10k"),
+            EnumChoice::new("marathon").with_label("Marathon (42.195 km)"),
+        ]);
+        let result = meta_attr.parse_attribute(Some("marathon")).unwrap();
+        assert!(matches!(&result, InstanceAttributes::Enum(Some(choice)) if choice.get_label() == "Marathon (42.195 km)"));
+        assert_eq!(result.to_string(), "marathon");
+        assert_eq!(
+            meta_attr.parse_attribute(Some("ultra")),
+            Err(AttributeError::NotAllowed {
+                value: "ultra".to_string(),
+                allowed: vec!["10k".to_string(), "marathon".to_string()]
+            })
+        );
+        assert_eq!(meta_attr.insert_none_for_type(), InstanceAttributes::Enum(None));
+    }
+
+    #[test]
+    fn test_parse_attribute_semantic_strings() {
+        let ref_link = MetaAttributes::Url.parse_attribute(Some("https://example.com/results")).unwrap();
+        assert_eq!(ref_link.to_string(), "https://example.com/results");
+        assert_eq!(
+            MetaAttributes::Url.parse_attribute(Some("example.com/results")),
+            Err(AttributeError::InvalidUrl("example.com/results".to_string()))
+        );
+        assert!(matches!(MetaAttributes::Email.parse_attribute(Some("contact@example")), Err(AttributeError::InvalidEmail(_))));
+        assert_eq!(MetaAttributes::Phone.parse_attribute(Some("+33 6 12 34 56 78")).unwrap().to_string(), "+33612345678");
+        assert!(matches!(MetaAttributes::Slug.parse_attribute(Some("Paris_2024")), Err(AttributeError::InvalidSlug(_))));
+        assert_eq!(MetaAttributes::Slug.insert_none_for_type(), InstanceAttributes::Slug(None));
+    }
+
+    #[test]
+    fn test_meta_entity_constraints() {
+        let entity = MetaEntity::new("country", MetaAttributes::Text)
+            .with_constraint(Constraint::Required)
+            .and_then(|entity| entity.with_constraint(Constraint::pattern("[A-Z]{2}")?))
+            .unwrap();
+        assert_eq!(entity.get_constraints().len(), 2);
+        assert!(entity.check(&InstanceAttributes::Text(Some("FR".to_string()))).is_ok());
+        assert_eq!(
+            entity.check(&InstanceAttributes::Text(None)),
+            Err(AttributeError::ConstraintViolation {
+                entity: "country".to_string(),
+                rule: "required".to_string(),
+                value: "Null".to_string()
+            })
+        );
+        assert_eq!(
+            MetaEntity::new("country", MetaAttributes::Text).with_constraint(Constraint::Min(Decimal::new(0, 0))).unwrap_err(),
+            AttributeError::InvalidConstraint {
+                entity: "country".to_string(),
+                rule: "min 0".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_meta_entity_default() {
+        let entity = MetaEntity::new("currency", MetaAttributes::Text).with_default("EUR").unwrap();
+        assert_eq!(entity.get_default(), Some(&InstanceAttributes::Text(Some("EUR".to_string()))));
+        assert!(MetaEntity::new("currency", MetaAttributes::Text).get_default().is_none());
+        assert_eq!(
+            MetaEntity::new("prize", MetaAttributes::I32).with_default("a lot").unwrap_err(),
+            AttributeError::InvalidType("Expect i32 got a lot".to_string())
+        );
+
+        let country = MetaEntity::new("country", MetaAttributes::Text).with_constraint(Constraint::MaxLength(2)).unwrap();
+        assert!(matches!(country.clone().with_default("FRA"), Err(AttributeError::ConstraintViolation { .. })));
+        let country = country.with_default("FR").unwrap();
+        assert!(matches!(
+            country.with_constraint(Constraint::pattern("[a-z]{2}").unwrap()),
+            Err(AttributeError::ConstraintViolation { value, .. }) if value == "FR"
+        ));
+    }
+
+    #[test]
+    fn test_parse_attribute_reference() {
+        let organizer = UniqueId::new("instance", None).unwrap();
+        let meta_attr = MetaAttributes::Reference(UniqueId::new("meta", None).unwrap());
+        assert_eq!(
+            meta_attr.parse_attribute(Some(&organizer.to_string())),
+            Ok(InstanceAttributes::Reference(Some(organizer)))
+        );
+        assert!(matches!(meta_attr.parse_attribute(Some("organizer")), Err(AttributeError::InvalidType(_))));
+        assert_eq!(meta_attr.insert_none_for_type(), InstanceAttributes::Reference(None));
+    }
+
+    #[test]
+    fn test_insert_none_for_type_list() {
+        let meta_attr = MetaAttributes::List(Box::new(MetaAttributes::Text));
+        assert_eq!(meta_attr.insert_none_for_type(), InstanceAttributes::List(Vec::new()));
+    }
+
     #[test]
     fn test_insert_none_for_type_text() {
         let meta_attr = MetaAttributes::Text;
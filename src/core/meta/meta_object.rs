@@ -1,15 +1,279 @@
 
+use std::collections::HashMap;
+
 use crate::core::model::object::Object;
 use crate::core::meta::meta_entity::{MetaEntity,MetaAttributes};
-use crate::core::errors::UniqueIdError;
+use crate::core::errors::{AttributeError, ExpressionError, UniqueIdError};
+use crate::core::expression::{expression_node::Expression, expression_parser::ExpressionParser, expression_value::ValueType};
+use crate::core::meta::meta_constraint::Constraint;
+use crate::core::meta::meta_rule::Rule;
+use crate::core::model::{entity::EntityTraits, Decimal};
+
+pub type MetaObject = Object<MetaEntity, MetaAttributes, MetaExtension>;
 
-pub type MetaObject = Object<MetaEntity, MetaAttributes>;
+#[derive(Debug, Clone, Default)]
+/// What a `MetaObject` carries besides its entities: the rules its instances are checked against, in order.
+pub struct MetaExtension {
+    rules: Vec<Rule>,
+}
 
 impl MetaObject {
     pub fn new_meta(name: &str) -> Result<Self,UniqueIdError> {
        Object::new(name, "meta", None)
     }
-   
+
+    /// Adds an entity like `update_entity`, checking its type and the computed entities and rules that read it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidType` if a `Decimal` scale is higher than `Decimal::MAX_SCALE`, or an
+    /// `AttributeError::ComputationFailed` or `AttributeError::InvalidRule` for the first computed entity or rule
+    /// the type breaks, the object being left unchanged.
+    pub fn add_entity(&mut self, name: &str, attribute: MetaAttributes) -> Result<(), AttributeError> {
+        Self::check_scales(&attribute)?;
+        let previous = self.entities.insert(name.to_string(), MetaEntity::new(name, attribute));
+        let checked = self.check_expressions();
+        if checked.is_err() {
+            match previous {
+                Some(previous) => self.insert_entity(previous),
+                None => {
+                    self.entities.remove(name);
+                }
+            }
+        }
+        checked
+    }
+
+    /// Adds an entity, along with its constraints, or replaces the entity of the same name.
+    pub fn insert_entity(&mut self, entity: MetaEntity) {
+        self.entities.insert(entity.get_name().to_string(), entity);
+    }
+
+    /// Marks an entity as required: instances cannot be built without a value for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::UnknownEntity` if the object has no entity of that name.
+    pub fn require(&mut self, name: &str) -> Result<(), AttributeError> {
+        let entity = self
+            .entities
+            .remove(name)
+            .ok_or_else(|| AttributeError::UnknownEntity(name.to_string()))?;
+        match entity.is_required() {
+            true => self.insert_entity(entity),
+            false => self.insert_entity(entity.with_constraint(Constraint::Required)?),
+        }
+        Ok(())
+    }
+
+    /// Sets the default value of an entity, parsed as its type and checked against its constraints.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::UnknownEntity` if the object has no entity of that name, or the error of
+    /// `MetaEntity::with_default` if the value is rejected, the entity being left unchanged.
+    pub fn set_default(&mut self, name: &str, input: &str) -> Result<(), AttributeError> {
+        let entity = self
+            .entities
+            .get(name)
+            .ok_or_else(|| AttributeError::UnknownEntity(name.to_string()))?;
+        let entity = entity.clone().with_default(input)?;
+        self.insert_entity(entity);
+        Ok(())
+    }
+
+    /// Returns the names of the required entities, sorted.
+    pub fn get_required_entities(&self) -> Vec<&str> {
+        let mut required = self
+            .entities
+            .values()
+            .filter(|entity| entity.is_required())
+            .map(|entity| entity.get_name())
+            .collect::<Vec<&str>>();
+        required.sort();
+        required
+    }
+
+    /// Makes an entity computed from the other entities of the object, e.g. `prize / distance`.
+    /// Instances cannot set computed entities, their values being computed whenever an entity they read changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpressionError::UnknownEntity` if the object has no entity of that name, the errors of
+    /// `ExpressionParser::parse` and `Expression::infer_type` if the expression is malformed or mistyped,
+    /// an `ExpressionError::IncompatibleResult` if the entity cannot hold the computed values,
+    /// or an `ExpressionError::CyclicComputation` if computed entities would depend on each other.
+    /// On error, the entity is left unchanged.
+    pub fn set_computed(&mut self, name: &str, source: &str) -> Result<(), ExpressionError> {
+        let entity = self
+            .entities
+            .get(name)
+            .ok_or_else(|| ExpressionError::UnknownEntity(name.to_string()))?;
+        let expression = ExpressionParser::parse(source)?;
+        self.check_computed(entity, &expression)?;
+
+        let previous = entity.clone();
+        self.insert_entity(previous.clone().with_expression(expression));
+        if let Err(error) = Self::computation_order(&self.entities) {
+            self.insert_entity(previous);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Adds a rule spanning several entities, or replaces the rule of the same name, e.g. `end_date > start_date`.
+    /// Instances are checked against the rules of their object on every update and when built.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of `ExpressionParser::parse` and `Expression::infer_type` if the expression is malformed
+    /// or mistyped, or an `ExpressionError::InvalidRule` if it does not compute a boolean.
+    pub fn add_rule(&mut self, name: &str, source: &str) -> Result<(), ExpressionError> {
+        let expression = ExpressionParser::parse(source)?;
+        self.check_rule(name, &expression)?;
+
+        let rule = Rule::new(name, source, expression);
+        match self.extension.rules.iter_mut().find(|rule| rule.get_name() == name) {
+            Some(existing) => *existing = rule,
+            None => self.extension.rules.push(rule),
+        }
+        Ok(())
+    }
+
+    /// Returns the rules of the object, in the order they were added.
+    pub fn get_rules(&self) -> &[Rule] {
+        &self.extension.rules
+    }
+
+    /// Checks that the expression of a computed entity is well typed and computes values the entity can hold.
+    fn check_computed(&self, entity: &MetaEntity, expression: &Expression) -> Result<(), ExpressionError> {
+        let found = expression.infer_type(&|reference| self.entity_type(reference))?;
+        if !found.fits(entity.get_attribute()) {
+            return Err(ExpressionError::IncompatibleResult {
+                entity: entity.get_name().to_string(),
+                expected: format!("{:?}", entity.get_attribute()),
+                found: found.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the expression of a rule is well typed and computes a boolean.
+    fn check_rule(&self, name: &str, expression: &Expression) -> Result<(), ExpressionError> {
+        let found = expression.infer_type(&|reference| self.entity_type(reference))?;
+        if found != ValueType::Bool {
+            return Err(ExpressionError::InvalidRule {
+                rule: name.to_string(),
+                found: found.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the `Decimal` scales of an attribute, elements of collections included, are at most `MAX_SCALE`.
+    fn check_scales(attribute: &MetaAttributes) -> Result<(), AttributeError> {
+        match attribute {
+            MetaAttributes::Decimal(scale) => Decimal::check_scale(*scale),
+            MetaAttributes::List(element) | MetaAttributes::Set(element) => Self::check_scales(element),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the expressions of every computed entity, sorted by name, then of every rule, in order.
+    fn check_expressions(&self) -> Result<(), AttributeError> {
+        let mut computed = self
+            .entities
+            .values()
+            .filter_map(|entity| entity.get_expression().map(|expression| (entity, expression)))
+            .collect::<Vec<(&MetaEntity, &Expression)>>();
+        computed.sort_by_key(|(entity, _)| entity.get_name());
+        computed
+            .into_iter()
+            .try_for_each(|(entity, expression)| {
+                self.check_computed(entity, expression).map_err(|error| AttributeError::ComputationFailed {
+                    entity: entity.get_name().to_string(),
+                    error,
+                })
+            })?;
+        self.extension
+            .rules
+            .iter()
+            .try_for_each(|rule| {
+                self.check_rule(rule.get_name(), rule.get_expression()).map_err(|error| AttributeError::InvalidRule {
+                    rule: rule.get_name().to_string(),
+                    error,
+                })
+            })
+    }
+
+    /// Returns the type expressions read an entity as.
+    fn entity_type(&self, name: &str) -> Result<ValueType, ExpressionError> {
+        let attribute = self
+            .entities
+            .get(name)
+            .map(|entity| entity.get_attribute())
+            .ok_or_else(|| ExpressionError::UnknownEntity(name.to_string()))?;
+        ValueType::of_attribute(attribute).ok_or_else(|| ExpressionError::UnsupportedEntity {
+            entity: name.to_string(),
+            attribute: format!("{:?}", attribute),
+        })
+    }
+
+    /// Returns the names of the computed entities in the order they must be computed,
+    /// every entity coming after the computed entities it reads.
+    pub fn get_computation_order(&self) -> Vec<&str> {
+        Self::computation_order(&self.entities).unwrap_or_default()
+    }
+
+    /// Sorts the computed entities so that every entity comes after the computed entities it reads,
+    /// independent entities being sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `ExpressionError::CyclicComputation` listing the entities along the first cycle met.
+    pub(crate) fn computation_order(entities: &HashMap<String, MetaEntity>) -> Result<Vec<&str>, ExpressionError> {
+        let mut computed = entities
+            .values()
+            .filter(|entity| entity.is_computed())
+            .map(|entity| entity.get_name())
+            .collect::<Vec<&str>>();
+        computed.sort();
+
+        let mut order = Vec::new();
+        computed
+            .into_iter()
+            .try_for_each(|name| Self::visit_computation(entities, name, &mut Vec::new(), &mut order))?;
+        Ok(order)
+    }
+
+    /// Adds a computed entity to the order after the computed entities it reads, depth first.
+    fn visit_computation<'a>(
+        entities: &'a HashMap<String, MetaEntity>,
+        name: &'a str,
+        path: &mut Vec<&'a str>,
+        order: &mut Vec<&'a str>,
+    ) -> Result<(), ExpressionError> {
+        if order.contains(&name) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|visited| *visited == name) {
+            let mut cycle = path[start..].iter().map(ToString::to_string).collect::<Vec<String>>();
+            cycle.push(name.to_string());
+            return Err(ExpressionError::CyclicComputation(cycle));
+        }
+        let Some(expression) = entities.get(name).and_then(|entity| entity.get_expression()) else {
+            return Ok(());
+        };
+
+        path.push(name);
+        expression
+            .get_references()
+            .into_iter()
+            .try_for_each(|reference| Self::visit_computation(entities, reference, path, order))?;
+        path.pop();
+        order.push(name);
+        Ok(())
+    }
 }
 
 
@@ -49,4 +313,150 @@ mod test {
         assert!(meta_object.entities.contains_key("attribute1"));
         assert!(meta_object.entities.contains_key("attribute2"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_require_entities() {
+        let mut meta_object = MetaObject::new_meta("TestMetaObject").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("distance", MetaAttributes::F64);
+        meta_object.update_entity("prize", MetaAttributes::I32);
+
+        meta_object.require("prize").unwrap();
+        meta_object.require("name").unwrap();
+        meta_object.require("name").unwrap();
+
+        assert_eq!(meta_object.get_required_entities(), vec!["name", "prize"]);
+        assert_eq!(meta_object.entities.get("name").unwrap().get_constraints().len(), 1);
+        assert_eq!(meta_object.require("year"), Err(AttributeError::UnknownEntity("year".to_string())));
+    }
+
+    #[test]
+    fn test_set_default() {
+        let mut meta_object = MetaObject::new_meta("TestMetaObject").unwrap();
+        meta_object.update_entity("country", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I32);
+
+        meta_object.set_default("country", "FR").unwrap();
+        assert!(meta_object.set_default("prize", "EUR").is_err());
+        assert!(meta_object.entities.get("prize").unwrap().get_default().is_none());
+        assert_eq!(meta_object.set_default("year", "2024"), Err(AttributeError::UnknownEntity("year".to_string())));
+        assert_eq!(
+            meta_object.entities.get("country").unwrap().get_default().map(ToString::to_string),
+            Some("FR".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_computed() {
+        let mut meta_object = MetaObject::new_meta("TestMetaObject").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("year", MetaAttributes::I32);
+        meta_object.update_entity("prize", MetaAttributes::I32);
+        meta_object.update_entity("distance", MetaAttributes::F64);
+        meta_object.update_entity("full_title", MetaAttributes::Text);
+        meta_object.update_entity("prize_per_km", MetaAttributes::F64);
+        meta_object.update_entity("label", MetaAttributes::Text);
+
+        meta_object.set_computed("prize_per_km", "prize / distance").unwrap();
+        meta_object.set_computed("label", "full_title + \": \" + prize_per_km").unwrap();
+        meta_object.set_computed("full_title", "name + \" (\" + year + \")\"").unwrap();
+        assert_eq!(meta_object.get_computation_order(), vec!["full_title", "prize_per_km", "label"]);
+        assert!(meta_object.entities.get("label").unwrap().is_computed());
+
+        assert_eq!(
+            meta_object.set_computed("year", "prize / distance"),
+            Err(ExpressionError::IncompatibleResult {
+                entity: "year".to_string(),
+                expected: "I32".to_string(),
+                found: "float".to_string()
+            })
+        );
+        assert_eq!(meta_object.set_computed("prize", "price * 2"), Err(ExpressionError::UnknownEntity("price".to_string())));
+        assert!(matches!(meta_object.set_computed("prize", "prize +"), Err(ExpressionError::InvalidSyntax { .. })));
+        assert!(!meta_object.entities.get("prize").unwrap().is_computed());
+    }
+
+    #[test]
+    fn test_set_computed_cycle() {
+        let mut meta_object = MetaObject::new_meta("TestMetaObject").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("title", MetaAttributes::Text);
+        meta_object.update_entity("label", MetaAttributes::Text);
+
+        meta_object.set_computed("title", "label + \"!\"").unwrap();
+        assert_eq!(
+            meta_object.set_computed("label", "name + title"),
+            Err(ExpressionError::CyclicComputation(vec![
+                "label".to_string(),
+                "title".to_string(),
+                "label".to_string()
+            ]))
+        );
+        assert!(!meta_object.entities.get("label").unwrap().is_computed());
+        assert_eq!(
+            meta_object.set_computed("name", "name"),
+            Err(ExpressionError::CyclicComputation(vec!["name".to_string(), "name".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_add_entity_checks_computed_entities_and_rules() {
+        let mut meta_object = MetaObject::new_meta("TestMetaObject").unwrap();
+        meta_object.update_entity("prize", MetaAttributes::I32);
+        meta_object.update_entity("distance", MetaAttributes::F64);
+        meta_object.update_entity("min_age", MetaAttributes::I16);
+        meta_object.update_entity("prize_per_km", MetaAttributes::F64);
+        meta_object.set_computed("prize_per_km", "prize / distance").unwrap();
+        meta_object.add_rule("ages", "min_age >= 0").unwrap();
+
+        assert!(matches!(
+            meta_object.add_entity("distance", MetaAttributes::Text),
+            Err(AttributeError::ComputationFailed { entity, error: ExpressionError::InvalidOperands { .. } }) if entity == "prize_per_km"
+        ));
+        assert_eq!(meta_object.entities.get("distance").unwrap().get_attribute(), &MetaAttributes::F64);
+        assert!(matches!(
+            meta_object.add_entity("min_age", MetaAttributes::Text),
+            Err(AttributeError::InvalidRule { rule, error: ExpressionError::InvalidOperands { .. } }) if rule == "ages"
+        ));
+        meta_object.add_entity("prize_per_km", MetaAttributes::I32).unwrap();
+        assert!(!meta_object.entities.get("prize_per_km").unwrap().is_computed());
+        meta_object.add_entity("distance", MetaAttributes::I64).unwrap();
+        meta_object.add_entity("city", MetaAttributes::Text).unwrap();
+        assert!(matches!(meta_object.add_entity("fee", MetaAttributes::Decimal(19)), Err(AttributeError::InvalidType(_))));
+        assert!(matches!(
+            meta_object.add_entity("fees", MetaAttributes::List(Box::new(MetaAttributes::Decimal(19)))),
+            Err(AttributeError::InvalidType(_))
+        ));
+        assert!(!meta_object.entities.contains_key("fee"));
+        meta_object.add_entity("fee", MetaAttributes::Decimal(Decimal::MAX_SCALE)).unwrap();
+    }
+
+    #[test]
+    fn test_add_rule() {
+        let mut meta_object = MetaObject::new_meta("TestMetaObject").unwrap();
+        meta_object.update_entity("start_date", MetaAttributes::Date(None));
+        meta_object.update_entity("end_date", MetaAttributes::Date(Some("%d/%m/%Y".to_string())));
+        meta_object.update_entity("tags", MetaAttributes::List(Box::new(MetaAttributes::Text)));
+        meta_object.update_entity("min_age", MetaAttributes::I16);
+
+        meta_object.add_rule("dates", "end_date > start_date").unwrap();
+        meta_object.add_rule("ages", "min_age >= 0").unwrap();
+        meta_object.add_rule("dates", "end_date >= start_date").unwrap();
+        assert_eq!(
+            meta_object.get_rules().iter().map(|rule| rule.get_source()).collect::<Vec<&str>>(),
+            vec!["end_date >= start_date", "min_age >= 0"]
+        );
+        assert_eq!(meta_object.get_rules()[0].get_entities(), vec!["end_date", "start_date"]);
+
+        assert_eq!(
+            meta_object.add_rule("age", "min_age + 1"),
+            Err(ExpressionError::InvalidRule {
+                rule: "age".to_string(),
+                found: "integer".to_string()
+            })
+        );
+        assert!(matches!(meta_object.add_rule("dates", "end_date > min_age"), Err(ExpressionError::InvalidOperands { .. })));
+        assert!(matches!(meta_object.add_rule("tags", "is_set(tags)"), Err(ExpressionError::UnsupportedEntity { .. })));
+        assert_eq!(meta_object.get_rules().len(), 2);
+    }
+}
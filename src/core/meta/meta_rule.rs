@@ -0,0 +1,112 @@
+//! # Rule
+//!
+//! A `Rule` is a validation rule declared on a `MetaObject`, spanning several of its entities,
+//! e.g. `end_date > start_date` or `status != "published" || is_set(ref_link)`.
+//!
+//! ## Evaluation:
+//! - A rule is an expression computing a boolean (see `Expression`), the rule holding when it computes `true`.
+//! - A rule computing nothing, because the entities it compares are missing, holds: missing values only break
+//!   `Required` constraints.
+//! - A broken rule is reported with the entities it reads, so that callers can point at the offending fields.
+
+use crate::core::{
+    errors::{AttributeError, ExpressionError},
+    expression::{expression_node::Expression, expression_value::Value},
+};
+
+#[derive(Debug, Clone)]
+/// A named rule on the values of several entities of an object.
+pub struct Rule {
+    name: String,
+    source: String,
+    expression: Expression,
+}
+
+impl Rule {
+    /// Creates a rule from its parsed expression, checked beforehand by `MetaObject::add_rule`.
+    pub(crate) fn new(name: &str, source: &str, expression: Expression) -> Self {
+        Rule {
+            name: name.to_string(),
+            source: source.to_string(),
+            expression,
+        }
+    }
+
+    /// Returns the name of the rule.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the expression of the rule as written.
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the parsed expression of the rule.
+    pub(crate) fn get_expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    /// Returns the names of the entities the rule reads, sorted.
+    pub fn get_entities(&self) -> Vec<&str> {
+        self.expression.get_references().into_iter().collect()
+    }
+
+    /// Checks the rule against the values of the entities, read with `entity_value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::RuleViolation` naming the rule and its entities if the rule computes `false`,
+    /// or an `AttributeError::InvalidRule` if it cannot be evaluated.
+    pub fn check(&self, entity_value: &dyn Fn(&str) -> Value) -> Result<(), AttributeError> {
+        let invalid = |error| AttributeError::InvalidRule {
+            rule: self.name.to_string(),
+            error,
+        };
+
+        match self.expression.evaluate(entity_value).map_err(invalid)? {
+            Value::Bool(true) | Value::Null => Ok(()),
+            Value::Bool(false) => Err(AttributeError::RuleViolation {
+                rule: self.name.to_string(),
+                entities: self.get_entities().into_iter().map(ToString::to_string).collect(),
+            }),
+            value => Err(invalid(ExpressionError::InvalidRule {
+                rule: self.name.to_string(),
+                found: value.get_type().map(|found| found.to_string()).unwrap_or_default(),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::expression::expression_parser::ExpressionParser;
+
+    fn rule(source: &str) -> Rule {
+        Rule::new("ages", source, ExpressionParser::parse(source).unwrap())
+    }
+
+    #[test]
+    fn test_check_rule() {
+        let values = |name: &str| match name {
+            "min_age" => Value::Integer(18),
+            "max_age" => Value::Integer(16),
+            _ => Value::Null,
+        };
+
+        assert_eq!(rule("max_age < 100").check(&values), Ok(()));
+        assert_eq!(rule("missing > min_age").check(&values), Ok(()));
+        assert_eq!(
+            rule("min_age < max_age").check(&values),
+            Err(AttributeError::RuleViolation {
+                rule: "ages".to_string(),
+                entities: vec!["max_age".to_string(), "min_age".to_string()]
+            })
+        );
+        assert!(matches!(
+            rule("min_age / (max_age - 16) > 1").check(&values),
+            Err(AttributeError::InvalidRule { rule, .. }) if rule == "ages"
+        ));
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! `MetaAttributes` describe the types of attributes that can be assigned to entities in a meta-object.
 //! These are used as blueprints for the instance objects to ensure type safety and proper initialization.
+//! Each `MetaEntity` can also declare `Constraint`s its values must follow, and each `MetaObject` `Rule`s
+//! spanning several of its entities.
 
+pub mod meta_constraint;
 pub mod meta_entity;
-pub mod meta_object;
\ No newline at end of file
+pub mod meta_object;
+pub mod meta_rule;
\ No newline at end of file
@@ -2,4 +2,5 @@ pub mod meta;
 pub mod model;
 pub mod template;
 pub mod instance;
-pub mod errors;
\ No newline at end of file
+pub mod errors;
+pub mod expression;
\ No newline at end of file
@@ -0,0 +1,263 @@
+//! Decimal Module
+//!
+//! This module provides an exact decimal number, stored as an integer amount of units at a fixed scale,
+//! so that amounts such as prizes and fees never go through floating point.
+//!
+
+use std::{cmp::Ordering, fmt};
+
+use crate::core::errors::AttributeError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// An exact decimal number: `units` divided by `10^scale`, e.g. `123450` at scale `2` for `1234.50`.
+pub struct Decimal {
+    units: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    /// Highest number of fractional digits a `Decimal` can hold.
+    pub const MAX_SCALE: u32 = 18;
+
+    /// Creates a new `Decimal` from its units and a scale known to be at most `MAX_SCALE`.
+    pub(crate) fn new(units: i128, scale: u32) -> Self {
+        debug_assert!(scale <= Self::MAX_SCALE, "decimal scale {scale} is higher than {}", Self::MAX_SCALE);
+        Decimal { units, scale }
+    }
+
+    /// Creates a new `Decimal` from its units and scale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidType` if `scale` is higher than `MAX_SCALE`.
+    pub fn try_new(units: i128, scale: u32) -> Result<Self, AttributeError> {
+        Self::check_scale(scale).map(|_| Decimal { units, scale })
+    }
+
+    /// Checks that a scale is at most `MAX_SCALE`.
+    pub(crate) fn check_scale(scale: u32) -> Result<(), AttributeError> {
+        match scale <= Self::MAX_SCALE {
+            true => Ok(()),
+            false => Err(AttributeError::InvalidType(format!(
+                "Expect a decimal scale of at most {} got {}",
+                Self::MAX_SCALE,
+                scale
+            ))),
+        }
+    }
+
+    /// Parses a decimal number such as `-1234.50`, its scale being the number of fractional digits written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidType` if the input is not a plain decimal number or is out of range.
+    pub fn parse(input: &str) -> Result<Decimal, AttributeError> {
+        let invalid = || AttributeError::InvalidType(format!("Expect decimal got {}", input));
+
+        let trimmed = input.trim();
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) || unsigned.ends_with('.') {
+            return Err(invalid());
+        }
+        let scale = u32::try_from(fraction.len())
+            .ok()
+            .filter(|scale| *scale <= Self::MAX_SCALE)
+            .ok_or_else(invalid)?;
+
+        let units = format!("{integer}{fraction}").parse::<i128>().map_err(|_| invalid())?;
+        Ok(Decimal::new(if negative { -units } else { units }, scale))
+    }
+
+    /// Returns the same number at a higher or equal scale, e.g. `1.5` at scale `2` gives `1.50`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::ScaleMismatch` if the number has more fractional digits than `scale`,
+    /// or an `AttributeError::InvalidType` if `scale` is higher than `MAX_SCALE` or the number cannot be
+    /// represented at that scale.
+    pub fn rescale(&self, scale: u32) -> Result<Decimal, AttributeError> {
+        Self::check_scale(scale)?;
+        if self.scale > scale {
+            return Err(AttributeError::ScaleMismatch {
+                expected: scale,
+                found: self.scale,
+            });
+        }
+        10i128
+            .checked_pow(scale - self.scale)
+            .and_then(|factor| self.units.checked_mul(factor))
+            .map(|units| Decimal::new(units, scale))
+            .ok_or_else(|| AttributeError::InvalidType(format!("Expect decimal of scale {} got {}", scale, self)))
+    }
+
+    /// Returns the amount of units, the number multiplied by `10^scale`.
+    pub fn get_units(&self) -> i128 {
+        self.units
+    }
+
+    /// Returns the number of fractional digits.
+    pub fn get_scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Returns whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.units == 0
+    }
+
+    /// Returns the number rounded half away from zero to a lower scale, or rescaled to a higher one,
+    /// e.g. `1.005` at scale `2` gives `1.01`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidType` if the number cannot be represented at that scale.
+    pub fn round(&self, scale: u32) -> Result<Decimal, AttributeError> {
+        if scale >= self.scale {
+            return self.rescale(scale);
+        }
+        let factor = 10i128.pow(self.scale - scale);
+        let (quotient, remainder) = (self.units / factor, self.units % factor);
+        let carry = match remainder.unsigned_abs() * 2 >= factor.unsigned_abs() {
+            true => self.units.signum(),
+            false => 0,
+        };
+        Ok(Decimal::new(quotient + carry, scale))
+    }
+
+    /// Adds two numbers, at the higher of their scales, `None` on overflow.
+    pub fn checked_add(&self, other: &Decimal) -> Option<Decimal> {
+        let scale = self.scale.max(other.scale);
+        let (left, right) = (self.rescale(scale).ok()?, other.rescale(scale).ok()?);
+        left.units.checked_add(right.units).map(|units| Decimal::new(units, scale))
+    }
+
+    /// Subtracts a number, at the higher of both scales, `None` on overflow.
+    pub fn checked_sub(&self, other: &Decimal) -> Option<Decimal> {
+        self.checked_add(&Decimal::new(other.units.checked_neg()?, other.scale))
+    }
+
+    /// Multiplies two numbers, the scale of the product being the sum of their scales, at most `MAX_SCALE`
+    /// where the product is rounded, `None` on overflow.
+    pub fn checked_mul(&self, other: &Decimal) -> Option<Decimal> {
+        // The exact product may hold up to twice `MAX_SCALE` fractional digits until it is rounded.
+        let product = Decimal {
+            units: self.units.checked_mul(other.units)?,
+            scale: self.scale + other.scale,
+        };
+        product.round(product.scale.min(Self::MAX_SCALE)).ok()
+    }
+
+    /// Compares the numbers the decimals stand for, whatever their scales, so that `1.5` equals `1.50`.
+    pub fn cmp_value(&self, other: &Decimal) -> Ordering {
+        let scale = self.scale.max(other.scale);
+        match (self.rescale(scale), other.rescale(scale)) {
+            (Ok(left), Ok(right)) => left.units.cmp(&right.units),
+            _ => self.to_f64().total_cmp(&other.to_f64()),
+        }
+    }
+
+    /// Returns the nearest `f64` to the number.
+    pub fn to_f64(&self) -> f64 {
+        self.units as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    /// Formats the number with `,` between groups of three integer digits, e.g. `-1,234.50`.
+    pub fn to_grouped_string(&self) -> String {
+        let (integer, fraction) = self.split_digits();
+        let grouped = integer
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|chunk| std::str::from_utf8(chunk).expect("digits are ASCII"))
+            .collect::<Vec<&str>>()
+            .join(",");
+        self.join_parts(&grouped, &fraction)
+    }
+
+    /// Splits the absolute value into its integer and fractional digits.
+    fn split_digits(&self) -> (String, String) {
+        let digits = format!("{:0>width$}", self.units.unsigned_abs(), width = self.scale as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        (integer.to_string(), fraction.to_string())
+    }
+
+    fn join_parts(&self, integer: &str, fraction: &str) -> String {
+        let sign = if self.units < 0 { "-" } else { "" };
+        match fraction.is_empty() {
+            true => format!("{sign}{integer}"),
+            false => format!("{sign}{integer}.{fraction}"),
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (integer, fraction) = self.split_digits();
+        write!(f, "{}", self.join_parts(&integer, &fraction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(Decimal::parse("1234.50"), Ok(Decimal::new(123450, 2)));
+        assert_eq!(Decimal::parse("-0.05"), Ok(Decimal::new(-5, 2)));
+        assert_eq!(Decimal::parse("+12"), Ok(Decimal::new(12, 0)));
+        assert!(Decimal::parse("1.").is_err());
+        assert!(Decimal::parse(".5").is_err());
+        assert!(Decimal::parse("1e3").is_err());
+        assert!(Decimal::parse("1,5").is_err());
+        assert_eq!(Decimal::parse("0.000000000000000001"), Ok(Decimal::new(1, 18)));
+        assert!(Decimal::parse("0.0000000000000000001").is_err());
+    }
+
+    #[test]
+    fn test_rescale_decimal() {
+        assert_eq!(Decimal::new(15, 1).rescale(3), Ok(Decimal::new(1500, 3)));
+        assert_eq!(
+            Decimal::new(1234, 3).rescale(2),
+            Err(AttributeError::ScaleMismatch { expected: 2, found: 3 })
+        );
+        assert!(matches!(Decimal::new(1, 0).rescale(19), Err(AttributeError::InvalidType(_))));
+        assert!(Decimal::new(1, 0).round(19).is_err());
+        assert_eq!(Decimal::try_new(1250, 2), Ok(Decimal::new(1250, 2)));
+        assert!(matches!(Decimal::try_new(1, 19), Err(AttributeError::InvalidType(_))));
+    }
+
+    #[test]
+    fn test_compare_decimal() {
+        assert_eq!(Decimal::new(15, 1).cmp_value(&Decimal::new(150, 2)), Ordering::Equal);
+        assert_eq!(Decimal::new(-1, 0).cmp_value(&Decimal::new(-5, 2)), Ordering::Less);
+        assert_eq!(Decimal::new(42195, 3).to_f64(), 42.195);
+    }
+
+    #[test]
+    fn test_decimal_arithmetic() {
+        assert_eq!(Decimal::new(1005, 3).round(2), Ok(Decimal::new(101, 2)));
+        assert_eq!(Decimal::new(-1005, 3).round(2), Ok(Decimal::new(-101, 2)));
+        assert_eq!(Decimal::new(1004, 3).round(0), Ok(Decimal::new(1, 0)));
+        assert_eq!(Decimal::new(15, 1).checked_add(&Decimal::new(25, 2)), Some(Decimal::new(175, 2)));
+        assert_eq!(Decimal::new(15, 1).checked_sub(&Decimal::new(2, 0)), Some(Decimal::new(-5, 1)));
+        assert_eq!(Decimal::new(15, 1).checked_mul(&Decimal::new(25, 2)), Some(Decimal::new(375, 3)));
+        assert_eq!(Decimal::new(i128::MAX, 0).checked_add(&Decimal::new(1, 0)), None);
+        assert_eq!(Decimal::new(15, 18).checked_mul(&Decimal::new(1, 1)), Some(Decimal::new(2, 18)));
+        assert_eq!(Decimal::new(1, 18).checked_add(&Decimal::new(1, 0)), Some(Decimal::new(1_000_000_000_000_000_001, 18)));
+    }
+
+    #[test]
+    fn test_display_decimal() {
+        assert_eq!(Decimal::new(123450, 2).to_string(), "1234.50");
+        assert_eq!(Decimal::new(-5, 2).to_string(), "-0.05");
+        assert_eq!(Decimal::new(7, 0).to_string(), "7");
+        assert_eq!(Decimal::new(-123456789, 2).to_grouped_string(), "-1,234,567.89");
+    }
+}
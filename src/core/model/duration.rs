@@ -0,0 +1,177 @@
+//! Duration Module
+//!
+//! This module provides `Duration`, a signed length of time with second precision backed by `chrono::TimeDelta`.
+//! Durations are written either as ISO 8601 durations (`PT2H5M30S`, `P1DT12H`) or as clock times (`2:05:30`, `2:05`).
+//!
+
+use std::fmt;
+
+use chrono::TimeDelta;
+
+use crate::core::errors::AttributeError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A length of time, such as the finishing time of a race.
+pub struct Duration(TimeDelta);
+
+impl Duration {
+    /// Creates a new `Duration` of the given number of seconds, `None` if it is out of the range of `TimeDelta`.
+    pub fn from_seconds(seconds: i64) -> Option<Self> {
+        TimeDelta::try_seconds(seconds).map(Duration)
+    }
+
+    /// Parses an ISO 8601 duration such as `PT2H5M30S`, or a clock time such as `2:05:30` or `-0:45`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidType` if the input is in neither format or is out of range.
+    pub fn parse(input: &str) -> Result<Duration, AttributeError> {
+        let trimmed = input.trim();
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, trimmed),
+        };
+
+        let seconds = match unsigned.strip_prefix('P') {
+            Some(iso) => Self::parse_iso(iso),
+            None => Self::parse_clock(unsigned),
+        };
+
+        seconds
+            .and_then(|seconds| Duration::from_seconds(if negative { -seconds } else { seconds }))
+            .ok_or_else(|| AttributeError::InvalidType(format!("Expect duration got {}", input)))
+    }
+
+    /// Returns the total number of seconds.
+    pub fn get_seconds(&self) -> i64 {
+        self.0.num_seconds()
+    }
+
+    /// Returns the underlying `chrono::TimeDelta`.
+    pub fn as_time_delta(&self) -> &TimeDelta {
+        &self.0
+    }
+
+    /// Returns whether the duration is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    /// Formats the duration as an ISO 8601 duration, e.g. `PT2H5M30S`.
+    pub fn to_iso_string(&self) -> String {
+        let (sign, hours, minutes, seconds) = self.split();
+        let mut output = format!("{sign}PT");
+        if hours > 0 {
+            output.push_str(&format!("{hours}H"));
+        }
+        if minutes > 0 {
+            output.push_str(&format!("{minutes}M"));
+        }
+        if seconds > 0 || (hours == 0 && minutes == 0) {
+            output.push_str(&format!("{seconds}S"));
+        }
+        output
+    }
+
+    /// Formats the duration in words, e.g. `2h 5m 30s`, leaving out leading zero units.
+    pub fn to_words_string(&self) -> String {
+        let (sign, hours, minutes, seconds) = self.split();
+        let output = match (hours, minutes) {
+            (0, 0) => format!("{seconds}s"),
+            (0, _) => format!("{minutes}m {seconds}s"),
+            _ => format!("{hours}h {minutes}m {seconds}s"),
+        };
+        format!("{sign}{output}")
+    }
+
+    /// Splits the duration into its sign, hours, minutes and seconds.
+    fn split(&self) -> (&str, u64, u64, u64) {
+        let total = self.get_seconds();
+        let sign = if total < 0 { "-" } else { "" };
+        let total = total.unsigned_abs();
+        (sign, total / 3600, total % 3600 / 60, total % 60)
+    }
+
+    /// Parses the part of an ISO 8601 duration following `P`: `nW`, or `nD` then `T` with `nH`, `nM`, `nS`.
+    fn parse_iso(iso: &str) -> Option<i64> {
+        let (date, time) = iso.split_once('T').unwrap_or((iso, ""));
+        if (date.is_empty() && time.is_empty()) || iso.ends_with('T') {
+            return None;
+        }
+
+        let mut seconds: i64 = 0;
+        for (part, units) in [(date, &[('W', 604_800), ('D', 86_400)][..]), (time, &[('H', 3600), ('M', 60), ('S', 1)][..])] {
+            let mut rest = part;
+            for (designator, factor) in units {
+                if let Some((value, tail)) = rest.split_once(*designator) {
+                    let value = value.parse::<i64>().ok()?;
+                    seconds = seconds.checked_add(value.checked_mul(*factor)?)?;
+                    rest = tail;
+                }
+            }
+            if !rest.is_empty() {
+                return None;
+            }
+        }
+        Some(seconds)
+    }
+
+    /// Parses a `H:MM:SS` or `H:MM` clock time, hours being unbounded.
+    fn parse_clock(clock: &str) -> Option<i64> {
+        let parts = clock.split(':').collect::<Vec<&str>>();
+        let (hours, minutes, seconds) = match parts[..] {
+            [hours, minutes] => (hours, minutes, "0"),
+            [hours, minutes, seconds] => (hours, minutes, seconds),
+            _ => return None,
+        };
+
+        let is_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        if !is_digits(hours) || !is_digits(minutes) || !is_digits(seconds) {
+            return None;
+        }
+        let (hours, minutes, seconds) = (hours.parse::<i64>().ok()?, minutes.parse::<i64>().ok()?, seconds.parse::<i64>().ok()?);
+        if minutes >= 60 || seconds >= 60 {
+            return None;
+        }
+        hours.checked_mul(3600)?.checked_add(minutes.checked_mul(60)?.checked_add(seconds)?)
+    }
+}
+
+impl fmt::Display for Duration {
+    /// Writes the duration as a clock time, e.g. `2:05:30`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (sign, hours, minutes, seconds) = self.split();
+        write!(f, "{sign}{hours}:{minutes:02}:{seconds:02}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(Duration::parse("2:05:30"), Ok(Duration::from_seconds(7530).unwrap()));
+        assert_eq!(Duration::parse("-0:45"), Ok(Duration::from_seconds(-2700).unwrap()));
+        assert_eq!(Duration::parse("PT2H5M30S"), Ok(Duration::from_seconds(7530).unwrap()));
+        assert_eq!(Duration::parse("P1DT12H"), Ok(Duration::from_seconds(129_600).unwrap()));
+        assert_eq!(Duration::parse("P2W"), Ok(Duration::from_seconds(1_209_600).unwrap()));
+        assert!(Duration::parse("2:65").is_err());
+        assert!(Duration::parse("PT").is_err());
+        assert!(Duration::parse("PT5M2H").is_err());
+        assert!(Duration::parse("two hours").is_err());
+        assert!(matches!(Duration::parse("PT9999999999999999S"), Err(AttributeError::InvalidType(_))));
+        assert!(matches!(Duration::parse("2000000000000000:00"), Err(AttributeError::InvalidType(_))));
+        assert!(matches!(Duration::parse("P99999999999999999999D"), Err(AttributeError::InvalidType(_))));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        let duration = Duration::from_seconds(7530).unwrap();
+        assert_eq!(duration.to_string(), "2:05:30");
+        assert_eq!(duration.to_iso_string(), "PT2H5M30S");
+        assert_eq!(duration.to_words_string(), "2h 5m 30s");
+        assert_eq!(Duration::from_seconds(-45).unwrap().to_words_string(), "-45s");
+        assert_eq!(Duration::from_seconds(0).unwrap().to_iso_string(), "PT0S");
+    }
+}
@@ -3,11 +3,21 @@
 //! The `Entity` struct represents a generic entity with a name and an attribute.
 //! The `EntityTraits` trait ensures a consistent interface for creating and interacting
 //! with entities across both meta and instance objects.
+//! It also provides the exact `Decimal` and `Money` value types, the `Duration` type, and the validated
+//! `Url`, `Email`, `Phone` and `Slug` strings, used by attributes.
 
 
+pub mod decimal;
+pub mod duration;
 pub mod entity;
+pub mod money;
 pub mod object;
+pub mod semantic;
 pub mod unique_id;
 
+pub use decimal::Decimal;
+pub use duration::Duration;
+pub use money::{Currency, Money};
+pub use semantic::{Email, Phone, Slug, Url};
 pub use unique_id::{UniqueId, UniqueIdParts};
 pub use object::Object;
\ No newline at end of file
@@ -0,0 +1,195 @@
+//! Money Module
+//!
+//! This module provides `Money`, an exact `Decimal` amount tied to an ISO-4217 `Currency`.
+//! The scale of an amount is given by the number of minor units of its currency, e.g. `2` for `EUR`, `0` for `JPY`.
+//!
+
+use std::fmt;
+
+use crate::core::errors::AttributeError;
+
+use super::decimal::Decimal;
+
+/// Supported currencies: ISO-4217 code, minor units and symbol, if it has a common one.
+const CURRENCIES: [(&str, u32, Option<&str>); 26] = [
+    ("AUD", 2, None),
+    ("BHD", 3, None),
+    ("BRL", 2, Some("R$")),
+    ("CAD", 2, None),
+    ("CHF", 2, None),
+    ("CNY", 2, None),
+    ("CZK", 2, None),
+    ("DKK", 2, None),
+    ("EUR", 2, Some("€")),
+    ("GBP", 2, Some("£")),
+    ("HKD", 2, None),
+    ("INR", 2, Some("₹")),
+    ("ISK", 0, None),
+    ("JPY", 0, Some("¥")),
+    ("KRW", 0, Some("₩")),
+    ("KWD", 3, None),
+    ("MXN", 2, None),
+    ("NOK", 2, None),
+    ("NZD", 2, None),
+    ("PLN", 2, None),
+    ("SEK", 2, None),
+    ("SGD", 2, None),
+    ("TND", 3, None),
+    ("USD", 2, Some("$")),
+    ("XOF", 0, None),
+    ("ZAR", 2, None),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// An ISO-4217 currency.
+pub struct Currency {
+    code: &'static str,
+    minor_units: u32,
+    symbol: Option<&'static str>,
+}
+
+impl Currency {
+    /// Looks a currency up by its ISO-4217 code, e.g. `EUR`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::UnknownCurrency` if the code is not a supported currency.
+    pub fn from_code(code: &str) -> Result<Currency, AttributeError> {
+        CURRENCIES
+            .iter()
+            .find(|(known, _, _)| known.eq_ignore_ascii_case(code.trim()))
+            .map(|(code, minor_units, symbol)| Currency {
+                code,
+                minor_units: *minor_units,
+                symbol: *symbol,
+            })
+            .ok_or_else(|| AttributeError::UnknownCurrency(code.to_string()))
+    }
+
+    /// Returns the ISO-4217 code of the currency.
+    pub fn get_code(&self) -> &str {
+        self.code
+    }
+
+    /// Returns the number of fractional digits of amounts in this currency.
+    pub fn get_minor_units(&self) -> u32 {
+        self.minor_units
+    }
+
+    /// Returns the symbol of the currency, if it has a common one.
+    pub fn get_symbol(&self) -> Option<&str> {
+        self.symbol
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// An exact amount of a given currency.
+pub struct Money {
+    amount: Decimal,
+    currency: Currency,
+}
+
+impl Money {
+    /// Parses an amount of `currency`, such as `1234.50`, `1234.50 EUR` or `EUR 1234.50`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::CurrencyMismatch` if another currency is written, an `AttributeError::ScaleMismatch`
+    /// if the amount has more fractional digits than the currency allows, or an `AttributeError::InvalidType`
+    /// if the input is not an amount.
+    pub fn parse(input: &str, currency: Currency) -> Result<Money, AttributeError> {
+        let is_code = |part: &str| part.len() == 3 && part.chars().all(|c| c.is_ascii_alphabetic());
+        let (amount, code) = match input.split_whitespace().collect::<Vec<&str>>()[..] {
+            [amount] => (amount, None),
+            [code, amount] | [amount, code] if is_code(code) => (amount, Some(code)),
+            _ => return Err(AttributeError::InvalidType(format!("Expect money got {}", input))),
+        };
+
+        if let Some(code) = code.filter(|code| !code.eq_ignore_ascii_case(currency.get_code())) {
+            return Err(AttributeError::CurrencyMismatch {
+                expected: currency.get_code().to_string(),
+                found: code.to_string(),
+            });
+        }
+
+        Decimal::parse(amount)?
+            .rescale(currency.get_minor_units())
+            .map(|amount| Money { amount, currency })
+    }
+
+    /// Returns the amount, at the scale of the currency.
+    pub fn get_amount(&self) -> &Decimal {
+        &self.amount
+    }
+
+    /// Returns the currency of the amount.
+    pub fn get_currency(&self) -> &Currency {
+        &self.currency
+    }
+
+    /// Formats the amount for display: grouped digits, prefixed by the currency symbol when there is one,
+    /// e.g. `€1,234.50` or `-$5.00`, and followed by the code otherwise, e.g. `1,234.500 BHD`.
+    pub fn to_currency_string(&self) -> String {
+        let grouped = self.amount.to_grouped_string();
+        match (self.currency.get_symbol(), grouped.strip_prefix('-')) {
+            (Some(symbol), Some(unsigned)) => format!("-{symbol}{unsigned}"),
+            (Some(symbol), None) => format!("{symbol}{grouped}"),
+            (None, _) => format!("{grouped} {}", self.currency),
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eur() -> Currency {
+        Currency::from_code("EUR").unwrap()
+    }
+
+    #[test]
+    fn test_currency_from_code() {
+        assert_eq!(Currency::from_code("jpy").unwrap().get_minor_units(), 0);
+        assert_eq!(Currency::from_code("XXX"), Err(AttributeError::UnknownCurrency("XXX".to_string())));
+    }
+
+    #[test]
+    fn test_parse_money() {
+        let expected = Money { amount: Decimal::new(123450, 2), currency: eur() };
+        assert_eq!(Money::parse("1234.5", eur()), Ok(expected));
+        assert_eq!(Money::parse("1234.50 EUR", eur()), Ok(expected));
+        assert_eq!(Money::parse("eur 1234.50", eur()), Ok(expected));
+        assert_eq!(
+            Money::parse("1234.50 USD", eur()),
+            Err(AttributeError::CurrencyMismatch { expected: "EUR".to_string(), found: "USD".to_string() })
+        );
+        assert_eq!(
+            Money::parse("1234.505", eur()),
+            Err(AttributeError::ScaleMismatch { expected: 2, found: 3 })
+        );
+        assert!(Money::parse("12 34", eur()).is_err());
+    }
+
+    #[test]
+    fn test_format_money() {
+        let prize = Money::parse("-1234.5", eur()).unwrap();
+        assert_eq!(prize.to_string(), "-1234.50 EUR");
+        assert_eq!(prize.to_currency_string(), "-€1,234.50");
+        let fee = Money::parse("1200.125", Currency::from_code("BHD").unwrap()).unwrap();
+        assert_eq!(fee.to_currency_string(), "1,200.125 BHD");
+        let entry = Money::parse("3000", Currency::from_code("JPY").unwrap()).unwrap();
+        assert_eq!(entry.to_currency_string(), "¥3,000");
+    }
+}
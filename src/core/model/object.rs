@@ -14,15 +14,17 @@ use super::{entity::EntityTraits, unique_id::Identifier, UniqueId};
 /// Represents a collection of entities.
 ///
 /// This can be either a `MetaObject` defining the schema or an `InstanceObject` containing real data.
-pub struct Object<E: EntityTraits<A>, A> {
+/// The `extension` holds what only one form carries, such as the rules of a `MetaObject`.
+pub struct Object<E: EntityTraits<A>, A, X = ()> {
     pub name: String,
     pub entities: HashMap<String, E>,
     pub id: UniqueId,
     pub meta_id: Option<UniqueId>,
+    pub extension: X,
     pub _marker: PhantomData<A>,
 }
 
-impl<E: EntityTraits<A> + Clone, A: Clone> Object<E, A> {
+impl<E: EntityTraits<A> + Clone, A: Clone, X: Clone + Default> Object<E, A, X> {
     /// Creates a new object with the given name and ID prefix.
     pub fn new(name: &str, prefix: &str, meta_id: Option<UniqueId>) -> Result<Self,UniqueIdError> {
         let unique_id_res=UniqueId::new(prefix,None);
@@ -32,6 +34,7 @@ impl<E: EntityTraits<A> + Clone, A: Clone> Object<E, A> {
                 entities: HashMap::new(),
                 id: unique_id,
                 meta_id: meta_id,
+                extension: X::default(),
                 _marker: PhantomData,
             }),
             Err(err) => Err(err),
@@ -78,7 +81,7 @@ impl<E: EntityTraits<A> + Clone, A: Clone> Object<E, A> {
     }
 }
 
-impl<E: EntityTraits<A> + Clone, A: Clone> Identifier for Object<E, A> {
+impl<E: EntityTraits<A> + Clone, A: Clone, X> Identifier for Object<E, A, X> {
     fn get_id(&self) -> &UniqueId {
         &self.id
     }
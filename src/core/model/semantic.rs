@@ -0,0 +1,230 @@
+//! Semantic Module
+//!
+//! This module provides strings that must follow a given shape: `Url`, `Email`, `Phone` and `Slug`.
+//! Each one is validated when parsed, so that a broken link or address never reaches a rendered template.
+//!
+
+use std::{fmt, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::core::errors::AttributeError;
+
+/// A host name made of dot-separated labels, e.g. `www.example.com`.
+const HOST: &str = r"[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?)*";
+
+static URL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"^(?i:https?)://(?P<host>{HOST})(?::[0-9]{{1,5}})?(?:[/?#]\S*)?$")).unwrap()
+});
+
+static EMAIL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"^[A-Za-z0-9!#$%&'*+/=?^_`{{|}}~-]+(?:\.[A-Za-z0-9!#$%&'*+/=?^_`{{|}}~-]+)*@{HOST}\.[A-Za-z]{{2,}}$")).unwrap()
+});
+
+static PHONE_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\+[1-9][0-9]{1,14}$").unwrap());
+
+static SLUG_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-z0-9]+(?:-[a-z0-9]+)*$").unwrap());
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An absolute `http` or `https` URL, such as the link to the registration page of a race.
+pub struct Url(String);
+
+impl Url {
+    /// Parses an absolute `http` or `https` URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidUrl` if the input is not such a URL.
+    pub fn parse(input: &str) -> Result<Url, AttributeError> {
+        let trimmed = input.trim();
+        match URL_PATTERN.is_match(trimmed) {
+            true => Ok(Url(trimmed.to_string())),
+            false => Err(AttributeError::InvalidUrl(input.to_string())),
+        }
+    }
+
+    /// Returns the host of the URL, lowercased, e.g. `www.example.com` for `https://WWW.example.com/register`.
+    pub fn get_host(&self) -> String {
+        URL_PATTERN
+            .captures(&self.0)
+            .and_then(|captures| captures.name("host"))
+            .map_or_else(String::new, |host| host.as_str().to_lowercase())
+    }
+
+    /// Returns the URL as written.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An email address, such as the contact of an organizer.
+pub struct Email(String);
+
+impl Email {
+    /// Parses an email address, its domain requiring a top-level domain, e.g. `contact@example.com`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidEmail` if the input is not an email address.
+    pub fn parse(input: &str) -> Result<Email, AttributeError> {
+        let trimmed = input.trim();
+        match EMAIL_PATTERN.is_match(trimmed) {
+            true => Ok(Email(trimmed.to_string())),
+            false => Err(AttributeError::InvalidEmail(input.to_string())),
+        }
+    }
+
+    /// Returns the address as a `mailto:` link, e.g. `mailto:contact@example.com`.
+    ///
+    /// Characters with a meaning in URIs, such as `?`, `&`, `%` or `#`, are percent-encoded so that an address
+    /// cannot add headers or a body to the link: `a?cc=b@example.com` gives `mailto:a%3Fcc%3Db@example.com`.
+    pub fn to_mailto(&self) -> String {
+        self.0.bytes().fold(String::from("mailto:"), |mut output, byte| {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'!' | b'$' | b'\'' | b'*' | b'+' | b'@' => {
+                    output.push(byte as char)
+                }
+                byte => output.push_str(&format!("%{:02X}", byte)),
+            }
+            output
+        })
+    }
+
+    /// Returns the address as written.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A phone number in the E.164 format, e.g. `+33123456789`.
+pub struct Phone(String);
+
+impl Phone {
+    /// Parses a phone number in the E.164 format, spaces, dots, dashes and parentheses being dropped,
+    /// so that `+33 1 23 45 67 89` gives `+33123456789`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidPhone` if the input is not an E.164 number.
+    pub fn parse(input: &str) -> Result<Phone, AttributeError> {
+        let number = input
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '.' | '-' | '(' | ')'))
+            .collect::<String>();
+        match PHONE_PATTERN.is_match(&number) {
+            true => Ok(Phone(number)),
+            false => Err(AttributeError::InvalidPhone(input.to_string())),
+        }
+    }
+
+    /// Returns the number as a `tel:` link, e.g. `tel:+33123456789`.
+    pub fn to_tel(&self) -> String {
+        format!("tel:{}", self.0)
+    }
+
+    /// Returns the number in the E.164 format.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A URL-friendly identifier made of lowercase letters and digits separated by single dashes, e.g. `paris-2024`.
+pub struct Slug(String);
+
+impl Slug {
+    /// Parses a slug, surrounding whitespace being dropped. A slug is never rewritten: `Paris 2024` is rejected
+    /// rather than turned into `paris-2024`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `AttributeError::InvalidSlug` if the input is not a slug.
+    pub fn parse(input: &str) -> Result<Slug, AttributeError> {
+        let trimmed = input.trim();
+        match SLUG_PATTERN.is_match(trimmed) {
+            true => Ok(Slug(trimmed.to_string())),
+            false => Err(AttributeError::InvalidSlug(input.to_string())),
+        }
+    }
+
+    /// Returns the slug.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Phone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Slug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_url() {
+        let url = Url::parse(" https://WWW.Paris-Marathon.com:443/register?lang=fr ").unwrap();
+        assert_eq!(url.as_str(), "https://WWW.Paris-Marathon.com:443/register?lang=fr");
+        assert_eq!(url.get_host(), "www.paris-marathon.com");
+        assert_eq!(Url::parse("http://localhost").unwrap().get_host(), "localhost");
+        assert_eq!(Url::parse("www.example.com"), Err(AttributeError::InvalidUrl("www.example.com".to_string())));
+        assert!(Url::parse("ftp://example.com").is_err());
+        assert!(Url::parse("https://exa mple.com").is_err());
+        assert!(Url::parse("https://-example.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_email() {
+        let email = Email::parse("contact@paris-marathon.com").unwrap();
+        assert_eq!(email.to_mailto(), "mailto:contact@paris-marathon.com");
+        assert!(Email::parse("first.last+race@mail.example.org").is_ok());
+        assert_eq!(Email::parse("contact@localhost"), Err(AttributeError::InvalidEmail("contact@localhost".to_string())));
+        assert!(Email::parse("contact.paris-marathon.com").is_err());
+        assert!(Email::parse("con..tact@example.com").is_err());
+        let injected = Email::parse("a?cc=boss&body=%41#x@example.com").unwrap();
+        assert_eq!(injected.to_mailto(), "mailto:a%3Fcc%3Dboss%26body%3D%2541%23x@example.com");
+        assert_eq!(Email::parse("first.last+race@mail.example.org").unwrap().to_mailto(), "mailto:first.last+race@mail.example.org");
+    }
+
+    #[test]
+    fn test_parse_phone() {
+        let phone = Phone::parse("+33 1 23 45 67 89").unwrap();
+        assert_eq!(phone.as_str(), "+33123456789");
+        assert_eq!(phone.to_tel(), "tel:+33123456789");
+        assert_eq!(Phone::parse("(+1) 212-555.0100").unwrap().as_str(), "+12125550100");
+        assert_eq!(Phone::parse("01 23 45 67 89"), Err(AttributeError::InvalidPhone("01 23 45 67 89".to_string())));
+        assert!(Phone::parse("+1234567890123456").is_err());
+    }
+
+    #[test]
+    fn test_parse_slug() {
+        assert_eq!(Slug::parse("paris-2024").unwrap().as_str(), "paris-2024");
+        assert_eq!(Slug::parse(" paris-2024\n").unwrap().as_str(), "paris-2024");
+        assert_eq!(Slug::parse("Paris 2024"), Err(AttributeError::InvalidSlug("Paris 2024".to_string())));
+        assert!(Slug::parse("paris--2024").is_err());
+        assert!(Slug::parse("-paris").is_err());
+        assert!(Slug::parse("").is_err());
+    }
+}
@@ -4,7 +4,7 @@
 //! A `UniqueId` is composed of three parts: a prefix, a timestamp, and a random key.
 //!
 
-use std::fmt;
+use std::{fmt, str::FromStr};
 use chrono::Utc;
 use rand::{distributions::Alphanumeric, Rng};
 
@@ -92,6 +92,43 @@ impl fmt::Display for UniqueId {
     }
 }
 
+impl FromStr for UniqueId {
+    type Err = UniqueIdError;
+
+    /// Reads a `UniqueId` back from its string representation, e.g. `instance:1712476800000:a1B2c3D4`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UniqueIdError::WrongFormat` if the input is not made of three parts, `UniqueIdError::MissingPrefix`
+    /// if the prefix is empty, or `UniqueIdError::InvalidPart` if the prefix holds whitespace, the timestamp is not
+    /// a positive number of milliseconds or the key is not alphanumeric.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim();
+        let parts = trimmed.split(':').collect::<Vec<&str>>();
+        let [prefix, timestamp, key] = parts[..] else {
+            return Err(UniqueIdError::WrongFormat(parts.len()));
+        };
+        let invalid = |part: &str, value: &str| UniqueIdError::InvalidPart {
+            part: part.to_string(),
+            value: value.to_string(),
+        };
+
+        if prefix.is_empty() {
+            return Err(UniqueIdError::MissingPrefix);
+        }
+        if prefix.chars().any(char::is_whitespace) {
+            return Err(invalid("prefix", prefix));
+        }
+        if timestamp.is_empty() || !timestamp.chars().all(|c| c.is_ascii_digit()) || timestamp.parse::<i64>().is_err() {
+            return Err(invalid("timestamp", timestamp));
+        }
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid("key", key));
+        }
+        Ok(UniqueId(trimmed.to_string()))
+    }
+}
+
 impl Identifier for UniqueId{
      /// Returns the string representation of the `UniqueId`.
     fn get_id(&self) -> &UniqueId {
@@ -136,6 +173,26 @@ mod tests {
         assert_eq!(key.len(), 10);
     }
 
+    #[test]
+    fn test_unique_id_from_str() {
+        let unique_id = UniqueId::new("instance", None).unwrap();
+        assert_eq!(unique_id.to_string().parse::<UniqueId>().unwrap(), unique_id);
+        assert!(matches!("instance:42".parse::<UniqueId>(), Err(UniqueIdError::WrongFormat(2))));
+        assert!(matches!(":1712476800000:a1B2c3D4".parse::<UniqueId>(), Err(UniqueIdError::MissingPrefix)));
+        assert!(matches!(
+            "instance:yesterday:a1B2c3D4".parse::<UniqueId>(),
+            Err(UniqueIdError::InvalidPart { part, .. }) if part == "timestamp"
+        ));
+        assert!(matches!(
+            "instance:1712476800000:a1-B2".parse::<UniqueId>(),
+            Err(UniqueIdError::InvalidPart { part, .. }) if part == "key"
+        ));
+        assert!(matches!(
+            "my instance:1712476800000:a1B2c3D4".parse::<UniqueId>(),
+            Err(UniqueIdError::InvalidPart { part, .. }) if part == "prefix"
+        ));
+    }
+
     #[test]
     fn test_invalid_format_error() {
         let unique_id = UniqueId("invalid_format".to_string());
@@ -10,7 +10,24 @@
 //!
 //! ### `Template`
 //! Represents a specific template tied to a parent `ObjectId`. Responsible for building the final content by
-//! injecting values from `InstanceObject` attributes into the placeholders.
+//! injecting values from `InstanceObject` attributes into the placeholders. `Template::render_to` streams the
+//! output into any `std::io::Write` sink instead of allocating a `String`. `Template::render_collection` renders
+//! every instance of an `InstanceObjectCollection`, optionally in parallel, with one result per instance.
+//!
+//! ### `TemplateParser`
+//! Turns `TemplateContent` into `TemplateNode`s: literal spans kept byte-for-byte and placeholder nodes.
+//!
+//! ### `Filter`
+//! Transforms placeholder values before they are written, type-checked against the `MetaAttributes` of the entity.
+//!
+//! ### `RenderContext`
+//! Options applied when rendering, such as the `NullPolicy` deciding what missing values render to:
+//! an error listing the entities, an empty string, the placeholder itself, or a fallback declared on the template.
+//! It also holds the instances that `Reference` entities are resolved in.
+//!
+//! ### `EscapeMode`
+//! Escapes substituted values for HTML, JSON strings, CSV fields or POSIX shell words. Selected per template,
+//! overridable per placeholder with the `escape:MODE` filter, and disabled with the `raw` filter.
 //!
 //! ### `TemplateBuilder`
 //! Facilitates the creation of a `Template`, validating that all required entities are present in the template content.
@@ -18,13 +35,44 @@
 //! ## Template Syntax:
 //! - Placeholders in templates follow the format: `[@attribute_name]`.
 //! - The `attribute_name` must match the entity names in the associated `MetaObject`.
+//! - Entities of nested objects are read with dotted paths: `[@address.city]`. Their types are checked when the
+//!   embedded `MetaObject`s are given to `TemplateBuilder::with_meta_objects`.
+//! - Entities of referenced instances are read the same way, `[@organizer.name]`, the instances being looked up
+//!   in the collection given to `RenderContext::with_instance_objects`.
+//! - Everything outside placeholders is copied verbatim, including newlines and indentation.
+//! - Filters are chained after the attribute name: `[@attribute_name|upper|truncate:20]` (see `Filter`).
+//! - Partials are included with `[>name]`, where `name` is the name or the id of a template stored in a
+//!   `TemplateCollection` given to `TemplateBuilder::with_partials`.
+//! - Layouts define named sections with `[#block name]...[#endblock]`. A template built with
+//!   `TemplateBuilder::extends` renders its layout, replacing the blocks it redefines.
+//! - Conditional sections follow the format: `[#if @attribute_name]...[#else]...[#endif]`, the `else` branch
+//!   being optional. A section is rendered when the attribute value is truthy (see `InstanceAttributes::is_truthy`).
+//! - Loops follow the format: `[#for item in @attribute_name separator=", "]...[#endfor]`, the separator being
+//!   optional. The body reads the element as `[@item]` and the loop state as `[@loop.index]`, `[@loop.first]`
+//!   and `[@loop.last]`. Only collection attributes can be looped over (see `MetaAttributes::is_collection`).
 //!
 //! ## Error Handling:
 //! The module defines `TemplateError` for various error scenarios:
 //! - `MissingMetaObjectId`: Raised when an `InstanceObject` lacks a parent `MetaObject` ID.
 //! - `UnauthrorisedActionFromMetaObject`: Raised when a `Template` is used with an incompatible `InstanceObject`.
 //! - `MissingEntitiesFromMetaObject`: Raised when required entities are missing from the `InstanceObject`.
+//! - `UnknownEntitiesInContent`: Raised when placeholders or conditions reference entities unknown to the `MetaObject`.
+//! - `InvalidFilter`: Raised when a filter does not accept the type of the value it receives.
+//! - `FilterFailed`: Raised at render time when a value cannot be processed by a filter.
+//! - `MissingPartial`, `IncompatiblePartial`, `CyclicPartial`: Raised when an included partial cannot be resolved,
+//!   references entities unknown to the including `MetaObject`, or includes the template back.
+//! - `IncompatibleLayout`, `UnknownBlocks`: Raised when a layout is bound to another `MetaObject`, or when
+//!   overridden blocks are not defined by the layout chain.
+//! - `NullEntities`: Raised at render time when values are missing and the `NullPolicy` does not allow it.
+//! - `InvalidLoop`: Raised when a loop iterates over an attribute that is not a collection.
+//! - `IoError`: Raised when the writer given to `Template::render_to` fails.
+//! - `InvalidSyntax`: Raised when a placeholder is empty or never closed, or when blocks are unbalanced.
 
 pub mod template;
 pub mod template_content;
-pub mod template_builder;
\ No newline at end of file
+pub mod template_builder;
+pub mod template_parser;
+pub mod template_filter;
+pub mod template_context;
+pub mod template_escape;
+pub mod template_renderer;
\ No newline at end of file
@@ -5,54 +5,128 @@
 //!
 //! ## Responsibilities:
 //! - Validates the relationship between the template and `InstanceObject`.
-//! - Populates placeholders with actual values.
+//! - Populates placeholders with actual values, for one `InstanceObject` or a whole `InstanceObjectCollection`.
+//! - Holds the fallback values used by `NullPolicy::Fallback` and the `EscapeMode` of substituted values.
+//! - Holds the partials resolved from a `TemplateCollection` when the template was built.
+//! - Holds the layout it extends, whose blocks are overridden by the ones of the template.
+//! - Holds the render plan, the `TemplateNode`s parsed and resolved against the layout once, when the template was built.
+//! - Holds the types of the entities it references, for templates including it to check theirs against.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::thread;
 
 use crate::core::errors::UniqueIdError;
-use crate::core::model::entity::EntityTraits;
 use crate::core::model::unique_id::Identifier;
+use crate::core::meta::meta_entity::MetaAttributes;
 use crate::core::model::UniqueId;
 use crate::{core::instance::instance_object::InstanceObject, core::errors::TemplateError};
+use crate::storage::in_memory::object_collections::InstanceObjectCollection;
 
 use super::template_content::TemplateContent;
+use super::template_context::RenderContext;
+use super::template_escape::EscapeMode;
+use super::template_parser::TemplateNode;
+use super::template_renderer::TemplateRenderer;
 
 
 /// Represents a template bound to a specific `ObjectId`.
 #[derive(Debug,Clone)]
 pub struct Template{
     id:UniqueId,
+    name: Option<String>,
     content: TemplateContent,
     parent_object: UniqueId,
+    fallbacks: HashMap<String, String>,
+    escape_mode: EscapeMode,
+    partials: HashMap<String, Template>,
+    layout: Option<Box<Template>>,
+    plan: Vec<TemplateNode>,
+    entity_types: HashMap<String, MetaAttributes>,
 }
 
 impl Template {
-    /// Creates a new `Template`.
-    pub fn new(content: TemplateContent, parent_object: &UniqueId) -> Result<Self, UniqueIdError> {
+    /// Creates a new `Template`, without render plan: templates are built, and their plan computed,
+    /// by the `TemplateBuilder`.
+    pub(crate) fn new(content: TemplateContent, parent_object: &UniqueId) -> Result<Self, UniqueIdError> {
        UniqueId::new("template", None).map(|unique_id|Template {
         id:unique_id,
+        name: None,
         content,
         parent_object:parent_object.clone(),
+        fallbacks: HashMap::new(),
+        escape_mode: EscapeMode::default(),
+        partials: HashMap::new(),
+        layout: None,
+        plan: Vec::new(),
+        entity_types: HashMap::new(),
     })
     }
-    /// Transforms the content by replacing placeholders with actual values from the `InstanceObject`.
-    fn transform_content(content: &str, object: &InstanceObject) -> String {
-        content
-            .split_whitespace()
-            .flat_map(|word| {
-                TemplateContent::get_pattern()
-                    .captures(TemplateContent::clean_word(word))
-                    .and_then(|caps| object.entities.get(&caps["reference"]))
-                    .map(|entity| entity.get_attribute().to_string())
-                    .or_else(|| Some(word.to_string()))
-            })
-            .collect::<Vec<String>>()
-            .join(" ")
-    }
-
-    /// Builds the final content by populating the template with values from the `InstanceObject`.
+
+    /// Sets the name the template can be included by.
+    pub(crate) fn with_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the fallback values, by entity name, used by `NullPolicy::Fallback`.
+    pub(crate) fn with_fallbacks(mut self, fallbacks: HashMap<String, String>) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
+    /// Sets the partials, by include key, resolved for the template and for the partials it includes.
+    pub(crate) fn with_partials(mut self, partials: HashMap<String, Template>) -> Self {
+        self.partials = partials;
+        self
+    }
+
+    /// Sets the layout the template extends.
+    pub(crate) fn with_layout(mut self, layout: Option<Template>) -> Self {
+        self.layout = layout.map(Box::new);
+        self
+    }
+
+    /// Sets the render plan, the nodes written for every instance.
+    pub(crate) fn with_plan(mut self, plan: Vec<TemplateNode>) -> Self {
+        self.plan = plan;
+        self
+    }
+
+    /// Sets the types of the entities referenced by the template and its partials, by entity name.
+    pub(crate) fn with_entity_types(mut self, entity_types: HashMap<String, MetaAttributes>) -> Self {
+        self.entity_types = entity_types;
+        self
+    }
+
+    /// Sets the escape mode applied to substituted values.
+    pub(crate) fn with_escape_mode(mut self, escape_mode: EscapeMode) -> Self {
+        self.escape_mode = escape_mode;
+        self
+    }
+
+    /// Builds the final content by populating the template with values from the `InstanceObject`,
+    /// using the default `RenderContext`.
     pub fn build_from_instance(&self, object: &InstanceObject) -> Result<String, TemplateError> {
+        self.render(object, &RenderContext::default())
+    }
+
+    /// Builds the final content by populating the template with values from the `InstanceObject`,
+    /// following the options of the `RenderContext`.
+    pub fn render(&self, object: &InstanceObject, context: &RenderContext) -> Result<String, TemplateError> {
+        let mut output = Vec::new();
+        self.render_to(object, context, &mut output)?;
+        String::from_utf8(output).map_err(|err| TemplateError::PropagatedError(err.to_string()))
+    }
+
+    /// Streams the content populated with values from the `InstanceObject` into a writer, such as a file,
+    /// a socket or a buffer, without building the whole output in memory.
+    ///
+    /// When an error is returned, part of the output may already have been written.
+    pub fn render_to<W: Write>(&self, object: &InstanceObject, context: &RenderContext, writer: &mut W) -> Result<(), TemplateError> {
         match &object.meta_id {
             Some(meta_id) if self.parent_object == *meta_id => {
-                Ok(Self::transform_content(&self.content.get_content(), object))
+                TemplateRenderer::new(object, context, self).render(&self.plan, writer)
             }
             Some(_) => Err(TemplateError::UnauthrorisedActionFromMetaObject {
                 meta: self.parent_object.get_id().to_string(), // Assuming `ObjectId` implements `Display`
@@ -62,6 +136,100 @@ impl Template {
         }
     }
 
+    /// Renders every instance of the parent `MetaObject` held by the collection.
+    ///
+    /// Each instance gets its own result, keyed by its id, so that one failure does not stop the batch.
+    pub fn render_collection(
+        &self,
+        collection: &InstanceObjectCollection,
+        context: &RenderContext,
+    ) -> HashMap<UniqueId, Result<String, TemplateError>> {
+        self.instances_of(collection)
+            .into_iter()
+            .map(|object| (object.get_id().clone(), self.render(object, context)))
+            .collect()
+    }
+
+    /// Same as `render_collection`, splitting the instances over the available cores.
+    pub fn render_collection_parallel(
+        &self,
+        collection: &InstanceObjectCollection,
+        context: &RenderContext,
+    ) -> HashMap<UniqueId, Result<String, TemplateError>> {
+        let objects = self.instances_of(collection);
+        let workers = thread::available_parallelism().map_or(1, |count| count.get());
+        let chunk_size = objects.len().div_ceil(workers).max(1);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = objects
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|object| (object.get_id().clone(), self.render(object, context)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("rendering thread panicked"))
+                .collect()
+        })
+    }
+
+    /// Returns the instances of the parent `MetaObject` held by the collection, none being an empty batch.
+    fn instances_of<'a>(&self, collection: &'a InstanceObjectCollection) -> Vec<&'a InstanceObject> {
+        collection
+            .get_objects_by_meta_id(&self.parent_object)
+            .unwrap_or_default()
+    }
+
+    /// Returns the fallback value declared for an entity.
+    pub fn get_fallback(&self, entity: &str) -> Option<&str> {
+        self.fallbacks.get(entity).map(String::as_str)
+    }
+
+    /// Returns the name the template can be included by.
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the raw content of the template.
+    pub fn get_content(&self) -> &TemplateContent {
+        &self.content
+    }
+
+    /// Returns the partial resolved for an include key, looking into the layout chain as well.
+    pub fn get_partial(&self, key: &str) -> Option<&Template> {
+        self.partials
+            .get(key)
+            .or_else(|| self.layout.as_ref().and_then(|layout| layout.get_partial(key)))
+    }
+
+    /// Returns the render plan: the parsed content, or the layout nodes with their blocks overridden
+    /// when the template extends a layout.
+    pub fn get_plan(&self) -> &[TemplateNode] {
+        &self.plan
+    }
+
+    /// Returns the type of an entity referenced by the template or its partials, as it was when built.
+    pub fn get_entity_type(&self, entity: &str) -> Option<&MetaAttributes> {
+        self.entity_types.get(entity)
+    }
+
+    /// Returns the layout the template extends.
+    pub fn get_layout(&self) -> Option<&Template> {
+        self.layout.as_deref()
+    }
+
+    /// Returns the escape mode applied to substituted values, unless a placeholder overrides it.
+    pub fn get_escape_mode(&self) -> EscapeMode {
+        self.escape_mode
+    }
+
     pub fn get_parent_object(&self)->&UniqueId{
         &self.parent_object
     }
@@ -75,7 +243,10 @@ impl Identifier for Template{
 
 #[cfg(test)]
 mod test {
-    use crate::core::{instance::instance_object::InstanceObjectBuilder, meta::{meta_entity::MetaAttributes, meta_object::MetaObject}, template::template_builder::TemplateBuilder};
+    use crate::core::{instance::instance_object::InstanceObjectBuilder, meta::{meta_entity::MetaAttributes, meta_object::MetaObject}, template::{template_builder::TemplateBuilder, template_context::NullPolicy}};
+    use crate::core::template::template_escape::EscapeMode;
+    use crate::core::{meta::meta_entity::EnumChoice, model::Currency};
+    use crate::storage::in_memory::{object_collections::MetaObjectCollection, template_collection::TemplateCollection};
 
     use super::*;
     
@@ -99,6 +270,25 @@ mod test {
         assert_eq!(populated_content, "This is a test with value1 and 123");
     }
 
+    #[test]
+    fn template_preserves_layout() {
+        // Test case for whitespace, newlines and punctuation surviving the rendering.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I16);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some("Paris Marathon")).unwrap();
+        instance_builder.update_entity("prize", Some("2030")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        let content = "Race:\t[@name]\n\n    Prize  ([@prize]),  see below.\n";
+        let template = TemplateBuilder::instanciate(content, &meta_object).build().unwrap();
+        let populated_content = template.build_from_instance(&instance_object).unwrap();
+
+        assert_eq!(populated_content, "Race:\tParis Marathon\n\n    Prize  (2030),  see below.\n");
+    }
+
     #[test]
     fn fail_template_creation_non_matching_entities() {
         // Test case for missing entities.
@@ -114,4 +304,693 @@ mod test {
             TemplateError::MissingEntitiesFromMetaObject(_)
         ));
     }
+
+    #[test]
+    fn fail_template_creation_invalid_syntax() {
+        // Test case for an unclosed placeholder.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("attribute1", MetaAttributes::Text);
+
+        let content = "This is a test with [@attribute1] and [@attribute1";
+        let template_result = TemplateBuilder::instanciate(content, &meta_object).build();
+
+        assert!(matches!(
+            template_result.unwrap_err(),
+            TemplateError::InvalidSyntax { position: 38, .. }
+        ));
+    }
+
+    #[test]
+    fn conditional_sections_follow_attribute_values() {
+        // Test case for a section vanishing when its attribute is not set.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I16);
+
+        let content = "[@name][#if @prize]\nPrize money: [@prize][#else]\nNo prize money[#endif]";
+        let template = TemplateBuilder::instanciate(content, &meta_object).build().unwrap();
+
+        let mut with_prize = InstanceObjectBuilder::new(&meta_object, "WithPrize");
+        with_prize.update_entity("name", Some("Paris")).unwrap();
+        with_prize.update_entity("prize", Some("2030")).unwrap();
+        let with_prize = with_prize.build().unwrap();
+
+        let mut without_prize = InstanceObjectBuilder::new(&meta_object, "WithoutPrize");
+        without_prize.update_entity("name", Some("Lyon")).unwrap();
+        without_prize.update_entity("prize", None).unwrap();
+        let without_prize = without_prize.build().unwrap();
+
+        assert_eq!(template.build_from_instance(&with_prize).unwrap(), "Paris\nPrize money: 2030");
+        assert_eq!(template.build_from_instance(&without_prize).unwrap(), "Lyon\nNo prize money");
+    }
+
+    #[test]
+    fn fail_template_creation_unknown_condition() {
+        // Test case for a condition referencing an entity missing from the MetaObject.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("attribute1", MetaAttributes::Text);
+
+        let content = "[@attribute1][#if @attribute2] and more[#endif]";
+        let template_result = TemplateBuilder::instanciate(content, &meta_object).build();
+
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::UnknownEntitiesInContent(vec!["attribute2".to_string()])
+        );
+    }
+
+    #[test]
+    fn filters_transform_values() {
+        // Test case for filters applied to placeholder values.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I16);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some(" Paris Marathon ")).unwrap();
+        instance_builder.update_entity("prize", Some("2030")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        let content = "[@name|trim|upper]: [@prize|pad:6] ([@prize|number])";
+        let template = TemplateBuilder::instanciate(content, &meta_object).build().unwrap();
+
+        assert_eq!(
+            template.build_from_instance(&instance_object).unwrap(),
+            "PARIS MARATHON: 002030 (2,030)"
+        );
+    }
+
+    #[test]
+    fn fail_template_creation_filter_type_mismatch() {
+        // Test case for a text-only filter applied to an I16 entity.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("prize", MetaAttributes::I16);
+
+        let template_result = TemplateBuilder::instanciate("[@prize|upper]", &meta_object).build();
+
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::InvalidFilter {
+                entity: "prize".to_string(),
+                filter: "upper".to_string(),
+                attribute: "I16".to_string()
+            }
+        );
+
+        let template_result = TemplateBuilder::instanciate("[@prize|pad:6|number]", &meta_object).build();
+
+        assert!(matches!(
+            template_result.unwrap_err(),
+            TemplateError::InvalidFilter { attribute, .. } if attribute == "Text"
+        ));
+    }
+
+    #[test]
+    fn default_filter_chains_with_typed_filters() {
+        // Test case for filters chained after a default that parses, or not, as the entity type.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("prize", MetaAttributes::I16);
+
+        let mut missing_builder = InstanceObjectBuilder::new(&meta_object, "Missing");
+        missing_builder.populate_missing_meta_entites();
+        let missing = missing_builder.build().unwrap();
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("prize", Some("2030")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        let padded = TemplateBuilder::instanciate("[@prize|default:0|pad:6]", &meta_object).build().unwrap();
+        assert_eq!(padded.build_from_instance(&missing).unwrap(), "000000");
+        assert_eq!(padded.build_from_instance(&instance_object).unwrap(), "002030");
+
+        let upper = TemplateBuilder::instanciate("[@prize|default:n/a|upper]", &meta_object).build().unwrap();
+        assert_eq!(upper.build_from_instance(&missing).unwrap(), "N/A");
+        assert_eq!(upper.build_from_instance(&instance_object).unwrap(), "2030");
+
+        let template_result = TemplateBuilder::instanciate("[@prize|default:n/a|pad:6]", &meta_object).build();
+        assert!(matches!(
+            template_result.unwrap_err(),
+            TemplateError::InvalidFilter { filter, .. } if filter == "pad"
+        ));
+    }
+
+    #[test]
+    fn null_policy_controls_missing_values() {
+        // Test case for each null policy on an instance without prize.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I16);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some("Paris")).unwrap();
+        instance_builder.populate_missing_meta_entites();
+        let instance_object = instance_builder.build().unwrap();
+
+        let content = "[@name]: [@prize]";
+        let template = TemplateBuilder::instanciate(content, &meta_object)
+            .with_fallback("prize", "to be announced")
+            .build()
+            .unwrap();
+        let render = |policy| template.render(&instance_object, &RenderContext::new().with_null_policy(policy));
+
+        assert_eq!(template.build_from_instance(&instance_object).unwrap(), "Paris: ");
+        assert_eq!(render(NullPolicy::Empty).unwrap(), "Paris: ");
+        assert_eq!(render(NullPolicy::KeepPlaceholder).unwrap(), "Paris: [@prize]");
+        assert_eq!(render(NullPolicy::Fallback).unwrap(), "Paris: to be announced");
+        assert_eq!(
+            render(NullPolicy::Error).unwrap_err(),
+            TemplateError::NullEntities(vec!["prize".to_string()])
+        );
+    }
+
+    #[test]
+    fn fallback_policy_requires_declared_value() {
+        // Test case for a missing value without fallback under NullPolicy::Fallback.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.populate_missing_meta_entites();
+        let instance_object = instance_builder.build().unwrap();
+
+        let template = TemplateBuilder::instanciate("Hello [@name]", &meta_object).build().unwrap();
+        let context = RenderContext::new().with_null_policy(NullPolicy::Fallback);
+
+        assert_eq!(
+            template.render(&instance_object, &context).unwrap_err(),
+            TemplateError::NullEntities(vec!["name".to_string()])
+        );
+    }
+
+    #[test]
+    fn fail_template_creation_unknown_fallback() {
+        // Test case for a fallback declared on an entity missing from the MetaObject.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+
+        let template_result = TemplateBuilder::instanciate("Hello [@name]", &meta_object)
+            .with_fallback("prize", "0")
+            .build();
+
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::UnknownEntitiesInContent(vec!["prize".to_string()])
+        );
+    }
+
+    #[test]
+    fn escape_mode_protects_substituted_values() {
+        // Test case for HTML escaping with per-placeholder overrides.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("ref_link", MetaAttributes::Text);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some("<script>alert('x')</script>")).unwrap();
+        instance_builder.update_entity("ref_link", Some("<a href=\"/paris\">Paris</a>")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        let content = "<h1>[@name]</h1>[@ref_link|raw]<script>var n = \"[@name|escape:json]\";</script>";
+        let template = TemplateBuilder::instanciate(content, &meta_object)
+            .with_escape_mode(EscapeMode::Html)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            template.build_from_instance(&instance_object).unwrap(),
+            "<h1>&lt;script&gt;alert(&#x27;x&#x27;)&lt;/script&gt;</h1><a href=\"/paris\">Paris</a>\
+            <script>var n = \"\\u003cscript\\u003ealert('x')\\u003c\\/script\\u003e\";</script>"
+        );
+    }
+
+    #[test]
+    fn partials_are_included() {
+        // Test case for a footer shared across templates of different MetaObjects.
+        let legal = MetaObject::new_meta("Legal").unwrap();
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+
+        let mut collection = TemplateCollection::new();
+        let footer = TemplateBuilder::instanciate("\n-- All rights reserved", &legal)
+            .with_name("footer")
+            .build()
+            .unwrap();
+        collection.insert(&footer).unwrap();
+        let signature = TemplateBuilder::instanciate("Regards, [@name|upper][>footer]", &meta_object)
+            .with_name("signature")
+            .with_partials(&collection)
+            .build()
+            .unwrap();
+        collection.insert(&signature).unwrap();
+
+        let content = "Hello [@name]\n[>signature]";
+        let template = TemplateBuilder::instanciate(content, &meta_object)
+            .with_partials(&collection)
+            .build()
+            .unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some("Paris")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        assert_eq!(
+            template.build_from_instance(&instance_object).unwrap(),
+            "Hello Paris\nRegards, PARIS\n-- All rights reserved"
+        );
+    }
+
+    #[test]
+    fn partials_keep_the_escape_mode_of_the_including_template() {
+        // Test case for a raw partial included by an HTML template, and an HTML partial included by a raw one.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+
+        let mut collection = TemplateCollection::new();
+        let raw_title = TemplateBuilder::instanciate("<b>[@name]</b>", &meta_object)
+            .with_name("raw_title")
+            .build()
+            .unwrap();
+        collection.insert(&raw_title).unwrap();
+        let html_title = TemplateBuilder::instanciate("<i>[@name]</i>", &meta_object)
+            .with_name("html_title")
+            .with_escape_mode(EscapeMode::Html)
+            .build()
+            .unwrap();
+        collection.insert(&html_title).unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some("<script>")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        let html = TemplateBuilder::instanciate("[>raw_title]", &meta_object)
+            .with_escape_mode(EscapeMode::Html)
+            .with_partials(&collection)
+            .build()
+            .unwrap();
+        assert_eq!(html.build_from_instance(&instance_object).unwrap(), "<b>&lt;script&gt;</b>");
+
+        let raw = TemplateBuilder::instanciate("[>html_title]", &meta_object)
+            .with_partials(&collection)
+            .build()
+            .unwrap();
+        assert_eq!(raw.build_from_instance(&instance_object).unwrap(), "<i>&lt;script&gt;</i>");
+    }
+
+    #[test]
+    fn fail_template_creation_missing_partial() {
+        // Test case for a partial absent from the collection, or no collection at all.
+        let meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        let collection = TemplateCollection::new();
+
+        let template_result = TemplateBuilder::instanciate("Body[>footer]", &meta_object)
+            .with_partials(&collection)
+            .build();
+        assert_eq!(template_result.unwrap_err(), TemplateError::MissingPartial("footer".to_string()));
+
+        let template_result = TemplateBuilder::instanciate("Body[>footer]", &meta_object).build();
+        assert_eq!(template_result.unwrap_err(), TemplateError::MissingPartial("footer".to_string()));
+    }
+
+    #[test]
+    fn fail_template_creation_incompatible_partial() {
+        // Test case for a partial referencing entities unknown to the including MetaObject.
+        let mut event = MetaObject::new_meta("Event").unwrap();
+        event.update_entity("organizer", MetaAttributes::Text);
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+
+        let mut collection = TemplateCollection::new();
+        let footer = TemplateBuilder::instanciate("By [@organizer]", &event).with_name("footer").build().unwrap();
+        collection.insert(&footer).unwrap();
+
+        let template_result = TemplateBuilder::instanciate("[@name][>footer]", &meta_object)
+            .with_partials(&collection)
+            .build();
+
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::IncompatiblePartial {
+                partial: "footer".to_string(),
+                meta: "TestMeta".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn fail_template_creation_partial_with_differently_typed_entity() {
+        // Test case for a partial referencing an entity of the same name but of another type.
+        let mut event = MetaObject::new_meta("Event").unwrap();
+        event.update_entity("prize", MetaAttributes::Text);
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("prize", MetaAttributes::I32);
+
+        let mut collection = TemplateCollection::new();
+        let footer = TemplateBuilder::instanciate("Prize: [@prize]", &event).with_name("footer").build().unwrap();
+        collection.insert(&footer).unwrap();
+
+        let template_result = TemplateBuilder::instanciate("[>footer]", &meta_object)
+            .with_partials(&collection)
+            .build();
+
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::IncompatiblePartial {
+                partial: "footer".to_string(),
+                meta: "TestMeta".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn fail_template_creation_cyclic_partials() {
+        // Test case for a new version of a partial including a template that includes the partial itself.
+        let meta_object = MetaObject::new_meta("TestMeta").unwrap();
+
+        let mut collection = TemplateCollection::new();
+        let header_v1 = TemplateBuilder::instanciate("Header", &meta_object).with_name("header").build().unwrap();
+        collection.insert(&header_v1).unwrap();
+        let footer = TemplateBuilder::instanciate("[>header] Footer", &meta_object)
+            .with_name("footer")
+            .with_partials(&collection)
+            .build()
+            .unwrap();
+        collection.insert(&footer).unwrap();
+        let header_v2 = TemplateBuilder::instanciate("[>footer] Header", &meta_object)
+            .with_name("header")
+            .with_partials(&collection)
+            .build()
+            .unwrap_err();
+        assert_eq!(header_v2, TemplateError::CyclicPartial(vec!["footer".to_string(), "header".to_string()]));
+    }
+
+    #[test]
+    fn layouts_are_extended() {
+        // Test case for a two-level inheritance chain overriding some blocks.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        meta_object.update_entity("prize", MetaAttributes::I16);
+
+        let base = "<title>[#block title][@name][#endblock]</title>\n[#block body]Prize: [@prize][#endblock]\n[#block footer]Base footer[#endblock]";
+        let base = TemplateBuilder::instanciate(base, &meta_object).build().unwrap();
+        let page = "[#block footer]Page footer[#endblock]";
+        let page = TemplateBuilder::instanciate(page, &meta_object).extends(&base).build().unwrap();
+        let race = "ignored\n[#block title]Race: [@name|upper][#endblock]";
+        let race = TemplateBuilder::instanciate(race, &meta_object).extends(&page).build().unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some("Paris")).unwrap();
+        instance_builder.update_entity("prize", Some("2030")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        assert_eq!(
+            race.build_from_instance(&instance_object).unwrap(),
+            "<title>Race: PARIS</title>\nPrize: 2030\nPage footer"
+        );
+        assert_eq!(
+            base.build_from_instance(&instance_object).unwrap(),
+            "<title>Paris</title>\nPrize: 2030\nBase footer"
+        );
+    }
+
+    #[test]
+    fn fail_template_creation_unknown_block() {
+        // Test case for a child overriding a block its layout does not define.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+
+        let base = TemplateBuilder::instanciate("[#block title][@name][#endblock]", &meta_object).build().unwrap();
+        let template_result = TemplateBuilder::instanciate("[#block sidebar]-[#endblock]", &meta_object)
+            .extends(&base)
+            .build();
+
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::UnknownBlocks(vec!["sidebar".to_string()])
+        );
+    }
+
+    #[test]
+    fn fail_template_creation_layout_of_other_meta_object() {
+        // Test case for a layout bound to another MetaObject.
+        let other = MetaObject::new_meta("Other").unwrap();
+        let meta_object = MetaObject::new_meta("TestMeta").unwrap();
+
+        let base = TemplateBuilder::instanciate("[#block title][#endblock]", &other).build().unwrap();
+        let template_result = TemplateBuilder::instanciate("[#block title]-[#endblock]", &meta_object)
+            .extends(&base)
+            .build();
+
+        assert!(matches!(
+            template_result.unwrap_err(),
+            TemplateError::IncompatibleLayout { meta, .. } if meta == "TestMeta"
+        ));
+    }
+
+    #[test]
+    fn render_streams_into_writer() {
+        // Test case for rendering into a writer, and for writer failures.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("name", Some("Paris")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        let template = TemplateBuilder::instanciate("Race: [@name]\n", &meta_object).build().unwrap();
+        let context = RenderContext::default();
+
+        let mut output = Vec::new();
+        template.render_to(&instance_object, &context, &mut output).unwrap();
+        template.render_to(&instance_object, &context, &mut output).unwrap();
+        assert_eq!(output, b"Race: Paris\nRace: Paris\n");
+
+        let mut full: &mut [u8] = &mut [0; 4];
+        assert!(matches!(
+            template.render_to(&instance_object, &context, &mut full),
+            Err(TemplateError::IoError(error)) if error.get_error().kind() == std::io::ErrorKind::WriteZero
+        ));
+    }
+
+    #[test]
+    fn collection_is_rendered_per_instance() {
+        // Test case for batch rendering, sequential and parallel, with a failing instance.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("name", MetaAttributes::Text);
+        let other = MetaObject::new_meta("Other").unwrap();
+
+        let mut collection = InstanceObjectCollection::new();
+        let mut expected = HashMap::new();
+        for name in ["Paris", "Lyon", "Nice"] {
+            let mut instance_builder = InstanceObjectBuilder::new(&meta_object, name);
+            instance_builder.update_entity("name", Some(name)).unwrap();
+            let instance_object = instance_builder.build().unwrap();
+            expected.insert(instance_object.get_id().clone(), Ok(format!("Race: {name}")));
+            collection.insert(&instance_object);
+        }
+        let mut missing_builder = InstanceObjectBuilder::new(&meta_object, "Missing");
+        missing_builder.populate_missing_meta_entites();
+        let missing = missing_builder.build().unwrap();
+        expected.insert(missing.get_id().clone(), Err(TemplateError::NullEntities(vec!["name".to_string()])));
+        collection.insert(&missing);
+        collection.insert(&InstanceObjectBuilder::new(&other, "Ignored").build().unwrap());
+
+        let template = TemplateBuilder::instanciate("Race: [@name]", &meta_object).build().unwrap();
+        let context = RenderContext::new().with_null_policy(NullPolicy::Error);
+
+        assert_eq!(template.render_collection(&collection, &context), expected);
+        assert_eq!(template.render_collection_parallel(&collection, &context), expected);
+        assert!(template.render_collection_parallel(&InstanceObjectCollection::new(), &context).is_empty());
+    }
+
+    #[test]
+    fn fail_template_creation_loop_over_single_value() {
+        // Test case for a loop over an entity that is not a collection.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("speakers", MetaAttributes::Text);
+
+        let content = "[#for speaker in @speakers][@speaker][#endfor]";
+        let template_result = TemplateBuilder::instanciate(content, &meta_object).build();
+
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::InvalidLoop {
+                entity: "speakers".to_string(),
+                attribute: "Text".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn loops_repeat_over_list_values() {
+        // Test case for loops with separator and loop state, and for the plain rendering of a list.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("speakers", MetaAttributes::List(Box::new(MetaAttributes::Text)));
+        meta_object.update_entity("tags", MetaAttributes::Set(Box::new(MetaAttributes::Text)));
+
+        let content = "[#for speaker in @speakers separator=\", \"][@loop.index]:[@speaker|upper][#if @loop.last]![#endif][#endfor] ([@tags])";
+        let template = TemplateBuilder::instanciate(content, &meta_object).build().unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("speakers", Some("Ada, Grace, Linus")).unwrap();
+        instance_builder.update_entity("tags", Some("rust, talk, rust")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+        assert_eq!(
+            template.build_from_instance(&instance_object).unwrap(),
+            "1:ADA, 2:GRACE, 3:LINUS! (rust, talk)"
+        );
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Empty");
+        instance_builder.populate_missing_meta_entites();
+        let instance_object = instance_builder.build().unwrap();
+        assert_eq!(template.build_from_instance(&instance_object).unwrap(), " ()");
+    }
+
+    #[test]
+    fn fail_template_creation_loop_element_filter_mismatch() {
+        // Test case for a filter that does not accept the element type of the looped list.
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("scores", MetaAttributes::List(Box::new(MetaAttributes::I16)));
+
+        let content = "[#for score in @scores][@score|upper][#endfor]";
+        let template_result = TemplateBuilder::instanciate(content, &meta_object).build();
+
+        assert!(matches!(template_result.unwrap_err(), TemplateError::InvalidFilter { entity, .. } if entity == "score"));
+        let content = "[#for score in @scores][@loop.count][#endfor]";
+        let template_result = TemplateBuilder::instanciate(content, &meta_object).build();
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::UnknownEntitiesInContent(vec!["loop.count".to_string()])
+        );
+    }
+
+    #[test]
+    fn money_renders_with_currency() {
+        // Test case for exact amounts rendered plainly and through the currency filter.
+        let eur = Currency::from_code("EUR").unwrap();
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("prize", MetaAttributes::Money(eur));
+        meta_object.update_entity("distance", MetaAttributes::Decimal(3));
+
+        let content = "[@prize|currency] ([@prize]) over [@distance|number] km";
+        let template = TemplateBuilder::instanciate(content, &meta_object).build().unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("prize", Some("45000")).unwrap();
+        instance_builder.update_entity("distance", Some("42.195")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        assert_eq!(
+            template.build_from_instance(&instance_object).unwrap(),
+            "€45,000.00 (45000.00 EUR) over 42.195 km"
+        );
+    }
+
+    #[test]
+    fn enum_renders_key_or_label() {
+        // Test case for an enumerated entity rendered by key and by label, in a loop over a list of choices.
+        let choices = vec![
+            EnumChoice::new("10k").with_label("10 km"),
+            EnumChoice::new("half"),
+        ];
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("category", MetaAttributes::Enum(choices.clone()));
+        meta_object.update_entity("others", MetaAttributes::List(Box::new(MetaAttributes::Enum(choices))));
+
+        let content = "[@category]: [@category|label|upper] / [#for other in @others separator=\"+\"][@other|label][#endfor]";
+        let template = TemplateBuilder::instanciate(content, &meta_object).build().unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance");
+        instance_builder.update_entity("category", Some("10k")).unwrap();
+        instance_builder.update_entity("others", Some("half, 10k")).unwrap();
+        assert!(instance_builder.update_entity("category", Some("ultra")).is_err());
+        let instance_object = instance_builder.build().unwrap();
+
+        assert_eq!(template.build_from_instance(&instance_object).unwrap(), "10k: 10 KM / half+10 km");
+    }
+
+    #[test]
+    fn nested_objects_render_dotted_references() {
+        // Test case for dotted references into an embedded MetaObject, directly and through a loop.
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        address.update_entity("city", MetaAttributes::Text);
+        address.update_entity("postcode", MetaAttributes::I32);
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&address);
+
+        let content = "[@address.city|upper][#if @address.postcode] ([@address.postcode|pad:5])[#endif]";
+        let template = TemplateBuilder::instanciate(content, &meta_object)
+            .with_meta_objects(&meta_objects)
+            .build()
+            .unwrap();
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "TestInstance").with_meta_objects(&meta_objects).unwrap();
+        instance_builder.update_entity("address.city", Some("Paris")).unwrap();
+        instance_builder.update_entity("address.postcode", Some("1000")).unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        assert_eq!(template.build_from_instance(&instance_object).unwrap(), "PARIS (01000)");
+    }
+
+    #[test]
+    fn fail_template_creation_unknown_nested_entity() {
+        // Test case for dotted references to entities the embedded MetaObject does not define.
+        let mut address = MetaObject::new_meta("Address").unwrap();
+        address.update_entity("city", MetaAttributes::Text);
+        let mut meta_object = MetaObject::new_meta("TestMeta").unwrap();
+        meta_object.update_entity("address", MetaAttributes::Object(address.get_id().clone()));
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&address);
+
+        let template_result = TemplateBuilder::instanciate("[@address.country]", &meta_object)
+            .with_meta_objects(&meta_objects)
+            .build();
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::UnknownEntitiesInContent(vec!["address.country".to_string()])
+        );
+
+        let template_result = TemplateBuilder::instanciate("[@address.city]", &meta_object).build();
+        assert_eq!(
+            template_result.unwrap_err(),
+            TemplateError::UnknownEntitiesInContent(vec!["address.city".to_string()])
+        );
+    }
+
+    #[test]
+    fn references_render_referenced_instances() {
+        // Test case for dotted references resolved through the instances given to the render context.
+        let mut organizer = MetaObject::new_meta("Organizer").unwrap();
+        organizer.update_entity("name", MetaAttributes::Text);
+        let mut meta_object = MetaObject::new_meta("Marathon").unwrap();
+        meta_object.update_entity("organizer", MetaAttributes::Reference(organizer.get_id().clone()));
+        let mut meta_objects = MetaObjectCollection::new();
+        meta_objects.insert(&organizer);
+
+        let template = TemplateBuilder::instanciate("Organized by [@organizer.name|upper]", &meta_object)
+            .with_meta_objects(&meta_objects)
+            .build()
+            .unwrap();
+
+        let mut organizer_builder = InstanceObjectBuilder::new(&organizer, "ASO");
+        organizer_builder.update_entity("name", Some("Amaury Sport")).unwrap();
+        let organizer_instance = organizer_builder.build().unwrap();
+        let mut instance_objects = InstanceObjectCollection::new();
+        instance_objects.insert(&organizer_instance);
+
+        let mut instance_builder = InstanceObjectBuilder::new(&meta_object, "Paris Marathon").with_instance_objects(&instance_objects);
+        instance_builder
+            .update_entity("organizer", Some(&organizer_instance.get_id().to_string()))
+            .unwrap();
+        let instance_object = instance_builder.build().unwrap();
+
+        let context = RenderContext::new().with_instance_objects(&instance_objects);
+        assert_eq!(template.render(&instance_object, &context).unwrap(), "Organized by AMAURY SPORT");
+
+        let context = RenderContext::new().with_null_policy(NullPolicy::Error);
+        assert_eq!(
+            template.render(&instance_object, &context).unwrap_err(),
+            TemplateError::NullEntities(vec!["organizer.name".to_string()])
+        );
+    }
 }
@@ -1,16 +1,30 @@
 //! # TemplateBuilder
 //!
 //! The `TemplateBuilder` struct provides a builder pattern for constructing `Template` instances.
-//! It validates that all required entities in the template match the associated `MetaObject`.
+//! It validates that all required entities in the template match the associated `MetaObject`,
+//! that placeholder filters accept the types of the entities they are applied to, and that loops iterate over collections.
+//! Fallback values used by `NullPolicy::Fallback` are declared here, per entity, as well as the `EscapeMode`.
+//! Partials are resolved through a `TemplateCollection` and stored in the built `Template`.
+//! A layout can be extended, in which case the overridden blocks must exist in the layout chain.
+//! The resolved nodes are kept in the `Template` as its render plan, so rendering never parses the content again.
 
-use crate::core::{errors::TemplateError, meta::meta_object::MetaObject, model::unique_id::Identifier};
-use super::{template::Template, template_content::TemplateContent};
+use std::collections::HashMap;
+
+use crate::core::{errors::TemplateError, meta::meta_object::MetaObject, model::{entity::EntityTraits, unique_id::Identifier, UniqueId}};
+use crate::storage::in_memory::{object_collections::MetaObjectCollection, template_collection::TemplateCollection};
+use super::{template::Template, template_content::TemplateContent, template_escape::EscapeMode, template_parser::{TemplateNode, TemplateParser}};
 
 
 /// Builder for creating and validating `Template` instances.
 pub struct TemplateBuilder<'a> {
     content: String,
     meta_object: &'a MetaObject,
+    name: Option<String>,
+    fallbacks: HashMap<String, String>,
+    escape_mode: EscapeMode,
+    partials: Option<&'a TemplateCollection<Template>>,
+    layout: Option<&'a Template>,
+    meta_objects: Option<&'a MetaObjectCollection>,
 }
 
 impl<'a> TemplateBuilder<'a> {
@@ -19,14 +33,196 @@ impl<'a> TemplateBuilder<'a> {
         TemplateBuilder {
             content: content.to_string(),
             meta_object,
+            name: None,
+            fallbacks: HashMap::new(),
+            escape_mode: EscapeMode::default(),
+            partials: None,
+            layout: None,
+            meta_objects: None,
         }
     }
 
+    /// Sets the name other templates can include this one by, as in `[>name]`.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Declares the value written for `entity` when it is missing and the `NullPolicy::Fallback` is used.
+    pub fn with_fallback(mut self, entity: &str, value: &str) -> Self {
+        self.fallbacks.insert(entity.to_string(), value.to_string());
+        self
+    }
+
+    /// Sets the escape mode applied to substituted values, `EscapeMode::Raw` by default.
+    pub fn with_escape_mode(mut self, escape_mode: EscapeMode) -> Self {
+        self.escape_mode = escape_mode;
+        self
+    }
+
+    /// Sets the collection `[>key]` partials are resolved from.
+    pub fn with_partials(mut self, partials: &'a TemplateCollection<Template>) -> Self {
+        self.partials = Some(partials);
+        self
+    }
+
+    /// Sets the collection the `MetaObject`s of `Object` and `Reference` entities are looked up in,
+    /// for dotted references such as `[@address.city]` or `[@organizer.name]` to be type-checked.
+    pub fn with_meta_objects(mut self, meta_objects: &'a MetaObjectCollection) -> Self {
+        self.meta_objects = Some(meta_objects);
+        self
+    }
+
+    /// Sets the layout the template extends. The content is then made of `[#block name]` overrides.
+    pub fn extends(mut self, layout: &'a Template) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
     /// Builds and validates a `Template`.
+    ///
+    /// Entities and filters are validated over the content, resolved against the layout chain,
+    /// and every included partial.
     pub fn build(self) -> Result<Template, TemplateError> {
         let template_content = TemplateContent::new(&self.content);
-        template_content.is_matching_entity(self.meta_object)?;
+        let plan = self.resolve_layout(template_content.parse()?)?;
+
+        let mut partials = HashMap::new();
+        let mut nodes = plan.clone();
+        nodes.extend(self.resolve_partials(&plan, &mut Vec::new(), &mut partials)?);
+
+        TemplateContent::is_matching_entity_nodes(&nodes, self.meta_object)?;
+        TemplateContent::is_matching_loops_nodes(&nodes, self.meta_object, self.meta_objects)?;
+        TemplateContent::is_matching_filters_nodes(&nodes, self.meta_object, self.meta_objects)?;
+        self.is_matching_fallbacks()?;
+        let entity_types = TemplateParser::collect_references(&nodes)
+            .into_iter()
+            .map(TemplateParser::root_reference)
+            .filter_map(|entity| {
+                let meta_entity = self.meta_object.entities.get(entity)?;
+                Some((entity.to_string(), meta_entity.get_attribute().clone()))
+            })
+            .collect();
         Template::new(template_content, self.meta_object.get_id())
+        .map(|template| {
+            template
+                .with_name(self.name)
+                .with_fallbacks(self.fallbacks)
+                .with_escape_mode(self.escape_mode)
+                .with_partials(partials)
+                .with_layout(self.layout.cloned())
+                .with_plan(plan)
+                .with_entity_types(entity_types)
+        })
         .map_err(|err|TemplateError::PropagatedError(err.to_string()))
     }
-}
\ No newline at end of file
+
+    /// Validates that fallback values are only declared for entities of the `MetaObject`.
+    fn is_matching_fallbacks(&self) -> Result<(), TemplateError> {
+        let mut unknown_entities: Vec<String> = self
+            .fallbacks
+            .keys()
+            .filter(|entity| !self.meta_object.entities.contains_key(entity.as_str()))
+            .cloned()
+            .collect();
+
+        match unknown_entities.is_empty() {
+            true => Ok(()),
+            false => {
+                unknown_entities.sort();
+                Err(TemplateError::UnknownEntitiesInContent(unknown_entities))
+            }
+        }
+    }
+
+    /// Overrides the blocks of the layout, if any, with the blocks of the content.
+    fn resolve_layout(&self, nodes: Vec<TemplateNode>) -> Result<Vec<TemplateNode>, TemplateError> {
+        let Some(layout) = self.layout else {
+            return Ok(nodes);
+        };
+
+        if layout.get_parent_object() != self.meta_object.get_id() {
+            return Err(TemplateError::IncompatibleLayout {
+                layout: layout.get_name().map_or_else(|| layout.get_id().to_string(), str::to_string),
+                meta: self.meta_object.get_name().to_string(),
+            });
+        }
+
+        let layout_nodes = layout.get_plan();
+        let layout_blocks = TemplateParser::collect_blocks(layout_nodes);
+        let blocks = TemplateParser::collect_blocks(&nodes);
+
+        let mut unknown_blocks: Vec<String> = blocks
+            .keys()
+            .filter(|name| !layout_blocks.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect();
+        if !unknown_blocks.is_empty() {
+            unknown_blocks.sort();
+            return Err(TemplateError::UnknownBlocks(unknown_blocks));
+        }
+
+        Ok(TemplateParser::override_blocks(layout_nodes, &blocks))
+    }
+
+    /// Resolves the partials included by the nodes, recursively, and returns the nodes of all of them.
+    ///
+    /// `chain` holds the partials being resolved, from the outermost one, to detect cycles.
+    fn resolve_partials(
+        &self,
+        nodes: &[TemplateNode],
+        chain: &mut Vec<(UniqueId, String)>,
+        partials: &mut HashMap<String, Template>,
+    ) -> Result<Vec<TemplateNode>, TemplateError> {
+        let mut partial_nodes = Vec::new();
+
+        for key in TemplateParser::collect_includes(nodes) {
+            let partial = self
+                .layout
+                .and_then(|layout| layout.get_partial(key))
+                .or_else(|| self.partials.and_then(|collection| collection.resolve(key).ok()))
+                .ok_or_else(|| TemplateError::MissingPartial(key.to_string()))?;
+
+            let is_self = (self.name.is_some() && partial.get_name() == self.name.as_deref())
+                || partial.get_content().get_content() == self.content;
+            if is_self || chain.iter().any(|(id, _)| id == partial.get_id()) {
+                let mut cycle: Vec<String> = chain.iter().map(|(_, key)| key.to_string()).collect();
+                cycle.push(key.to_string());
+                return Err(TemplateError::CyclicPartial(cycle));
+            }
+
+            let nodes = partial.get_plan();
+            self.is_compatible_partial(key, partial, nodes)?;
+
+            chain.push((partial.get_id().clone(), key.to_string()));
+            let nested_nodes = self.resolve_partials(nodes, chain, partials)?;
+            chain.pop();
+
+            partials.insert(key.to_string(), partial.clone());
+            partial_nodes.extend_from_slice(nodes);
+            partial_nodes.extend(nested_nodes);
+        }
+
+        Ok(partial_nodes)
+    }
+
+    /// Validates that a partial bound to another `MetaObject` only references entities of this one, of the same types.
+    fn is_compatible_partial(&self, key: &str, partial: &Template, nodes: &[TemplateNode]) -> Result<(), TemplateError> {
+        let compatible = partial.get_parent_object() == self.meta_object.get_id()
+            || TemplateParser::collect_references(nodes).iter().all(|reference| {
+                let entity = TemplateParser::root_reference(reference);
+                self.meta_object
+                    .entities
+                    .get(entity)
+                    .is_some_and(|meta_entity| partial.get_entity_type(entity) == Some(meta_entity.get_attribute()))
+            });
+
+        match compatible {
+            true => Ok(()),
+            false => Err(TemplateError::IncompatiblePartial {
+                partial: key.to_string(),
+                meta: self.meta_object.get_name().to_string(),
+            }),
+        }
+    }
+}
@@ -2,14 +2,25 @@
 //!
 //! The `TemplateContent` struct wraps around the raw template string. It provides:
 //! - Placeholder pattern matching using `Regex`.
+//! - Parsing into `TemplateNode`s through the `TemplateParser`.
 //! - Methods to validate and extract entities from the template.
 
 
 use std::collections::HashSet;
+use std::sync::LazyLock;
 use regex::Regex;
 
 use crate::{core::model::{entity::EntityTraits, object::Object}, core::errors::TemplateError};
+use crate::core::meta::{meta_entity::MetaAttributes, meta_object::MetaObject};
+use crate::storage::in_memory::object_collections::MetaObjectCollection;
 
+use super::template_parser::{Loop, TemplateNode, TemplateParser};
+
+
+/// Pattern shared by every template, compiled once on first use.
+static PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[(?:@(?P<reference>[^\[\]]*)|#(?P<directive>[^\[\]]*)|>(?P<partial>[^\[\]]*))\]").unwrap()
+});
 
 /// Encapsulates the raw content of a template.
 #[derive(Debug,Clone)]
@@ -26,28 +37,49 @@ impl TemplateContent {
         &self.0
     }
 
-    /// Returns the regular expression pattern for template placeholders.
-    pub fn get_pattern() -> Regex {
-        Regex::new(r"\[@(?P<reference>[^\]]+)\]").unwrap()
+    /// Returns the regular expression pattern for template placeholders (`[@...]`), directives (`[#...]`)
+    /// and partials (`[>...]`).
+    pub fn get_pattern() -> &'static Regex {
+        &PATTERN
     }
 
-    /// Cleans a word by stripping any non-alphanumeric characters, except for template-specific symbols.
-    pub fn clean_word(word: &str) -> &str {
-        word.trim_matches(|c: char| !c.is_alphanumeric() && c != '@' && c != '[' && c != ']')
+    /// Parses the content into literal spans, placeholder nodes and directive blocks.
+    pub fn parse(&self) -> Result<Vec<TemplateNode>, TemplateError> {
+        TemplateParser::parse(&self.0)
     }
 
-    /// Validates that all entities in the `Object` are referenced in the template.
-    pub fn is_matching_entity<E: EntityTraits<A>, A>(
+    /// Validates that all entities in the `Object` are referenced in the template,
+    /// and that the template references nothing else, conditions included.
+    pub fn is_matching_entity<E: EntityTraits<A>, A, X>(
         &self,
-        object: &Object<E, A>,
+        object: &Object<E, A, X>,
     ) -> Result<(), TemplateError> {
-        let content_entities: HashSet<_> = Self::get_pattern()
-            .captures_iter(&self.0)
-            .filter_map(|caps| caps.name("reference").map(|m| m.as_str()))
+        Self::is_matching_entity_nodes(&self.parse()?, object)
+    }
+
+    /// Validates that all entities in the `Object` are referenced by the nodes, and nothing else.
+    ///
+    /// A dotted reference such as `address.city` references the `address` entity.
+    pub fn is_matching_entity_nodes<E: EntityTraits<A>, A, X>(
+        nodes: &[TemplateNode],
+        object: &Object<E, A, X>,
+    ) -> Result<(), TemplateError> {
+        let content_entities: HashSet<&str> = TemplateParser::collect_references(nodes)
+            .into_iter()
+            .map(TemplateParser::root_reference)
             .collect();
 
         let object_entities: HashSet<&str> = object.entities.keys().map(|k| k.as_str()).collect();
 
+        let mut unknown_entities: Vec<String> = content_entities
+            .difference(&object_entities)
+            .map(|v| v.to_string())
+            .collect();
+        if !unknown_entities.is_empty() {
+            unknown_entities.sort();
+            return Err(TemplateError::UnknownEntitiesInContent(unknown_entities));
+        }
+
         if content_entities == object_entities {
             Ok(())
         } else {
@@ -59,4 +91,140 @@ impl TemplateContent {
             ))
         }
     }
-}
\ No newline at end of file
+
+    /// Validates that every filter chain accepts the `MetaAttributes` of the entity it is applied to.
+    pub fn is_matching_filters(&self, meta_object: &MetaObject) -> Result<(), TemplateError> {
+        Self::is_matching_filters_nodes(&self.parse()?, meta_object, None)
+    }
+
+    /// Validates that every filter chain of the nodes accepts the `MetaAttributes` of its entity,
+    /// loop elements taking the element type of their collection.
+    ///
+    /// The types of dotted references are looked up in the `MetaObject`s of `Object` and `Reference` entities.
+    pub fn is_matching_filters_nodes(
+        nodes: &[TemplateNode],
+        meta_object: &MetaObject,
+        meta_objects: Option<&MetaObjectCollection>,
+    ) -> Result<(), TemplateError> {
+        let mut result = Ok(());
+        TemplateParser::walk_scoped(nodes, &mut Vec::new(), &mut |node, scope| {
+            let TemplateNode::Placeholder(placeholder) = node else {
+                return;
+            };
+            let Some(attribute) = Self::resolve_type(placeholder.get_reference(), scope, meta_object, meta_objects) else {
+                return;
+            };
+            if result.is_ok() {
+                result = placeholder
+                    .get_filters()
+                    .iter()
+                    .try_fold(attribute, |attribute, filter| {
+                        filter.output_type(&attribute).ok_or_else(|| TemplateError::InvalidFilter {
+                            entity: placeholder.get_reference().to_string(),
+                            filter: filter.get_name().to_string(),
+                            attribute: format!("{:?}", attribute),
+                        })
+                    })
+                    .map(|_| ());
+            }
+        });
+        result
+    }
+
+    /// Validates that loops iterate over collections, and that the references bound by loops and the dotted
+    /// references exist.
+    pub fn is_matching_loops_nodes(
+        nodes: &[TemplateNode],
+        meta_object: &MetaObject,
+        meta_objects: Option<&MetaObjectCollection>,
+    ) -> Result<(), TemplateError> {
+        let mut result = Ok(());
+        TemplateParser::walk_scoped(nodes, &mut Vec::new(), &mut |node, scope| {
+            let unknown = |reference: &str| Self::unknown_reference(reference, scope, meta_object, meta_objects);
+            let error = match node {
+                TemplateNode::Loop(body) => match Self::resolve_type(body.get_reference(), scope, meta_object, meta_objects) {
+                    Some(attribute) if !attribute.is_collection() => Some(TemplateError::InvalidLoop {
+                        entity: body.get_reference().to_string(),
+                        attribute: format!("{:?}", attribute),
+                    }),
+                    Some(_) => None,
+                    None => unknown(body.get_reference()),
+                },
+                TemplateNode::Placeholder(placeholder) => unknown(placeholder.get_reference()),
+                TemplateNode::Conditional(conditional) => unknown(conditional.get_reference()),
+                TemplateNode::Literal(_) | TemplateNode::Include(_) | TemplateNode::Block(_) => None,
+            };
+            if let (Ok(()), Some(error)) = (&result, error) {
+                result = Err(error);
+            }
+        });
+        result
+    }
+
+    /// Returns the type of a reference, as bound by the innermost enclosing loop or declared by the `MetaObject`,
+    /// following the dotted path through embedded and referenced `MetaObject`s.
+    fn resolve_type(
+        reference: &str,
+        scope: &[&Loop],
+        meta_object: &MetaObject,
+        meta_objects: Option<&MetaObjectCollection>,
+    ) -> Option<MetaAttributes> {
+        let (root, path) = match reference.split_once('.') {
+            Some((root, path)) => (root, Some(path)),
+            None => (reference, None),
+        };
+
+        let attribute = match scope.iter().rposition(|body| body.binds(reference)) {
+            Some(_) if root == Loop::STATE => {
+                return match path {
+                    Some("index") => Some(MetaAttributes::U64),
+                    Some("first" | "last") => Some(MetaAttributes::Bool),
+                    _ => None,
+                }
+            }
+            Some(depth) => Self::resolve_type(scope[depth].get_reference(), &scope[..depth], meta_object, meta_objects)?
+                .get_element_type()
+                .cloned()?,
+            None => meta_object.entities.get(root)?.get_attribute().clone(),
+        };
+
+        path.map_or(Some(attribute.clone()), |path| {
+            path.split('.').try_fold(attribute, |attribute, field| match attribute {
+                MetaAttributes::Object(meta_id) | MetaAttributes::Reference(meta_id) => meta_objects?
+                    .get(&meta_id)
+                    .ok()?
+                    .entities
+                    .get(field)
+                    .map(|entity| entity.get_attribute().clone()),
+                _ => None,
+            })
+        })
+    }
+
+    /// Returns an error when a reference bound by a loop, or a dotted reference, does not resolve to a value.
+    ///
+    /// Other unknown references are reported by `is_matching_entity_nodes`.
+    fn unknown_reference(
+        reference: &str,
+        scope: &[&Loop],
+        meta_object: &MetaObject,
+        meta_objects: Option<&MetaObjectCollection>,
+    ) -> Option<TemplateError> {
+        let is_checked = TemplateParser::is_local(reference, scope) || reference.contains('.');
+        match is_checked && Self::resolve_type(reference, scope, meta_object, meta_objects).is_none() {
+            true => Some(TemplateError::UnknownEntitiesInContent(vec![reference.to_string()])),
+            false => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pattern_is_shared() {
+        assert!(std::ptr::eq(TemplateContent::get_pattern(), TemplateContent::get_pattern()));
+        assert_eq!(TemplateContent::get_pattern().find_iter("[@a] [#if @b] [>c] [d]").count(), 3);
+    }
+}
@@ -0,0 +1,59 @@
+//! # RenderContext
+//!
+//! The `RenderContext` gathers the options used when a `Template` is rendered with an `InstanceObject`.
+//!
+//! ## Options:
+//! - `NullPolicy`: what to write when a placeholder value is missing.
+//! - Instance objects: the instances `Reference` entities are resolved in, e.g. for `[@organizer.name]`.
+
+use crate::storage::in_memory::object_collections::InstanceObjectCollection;
+
+/// Decides what a placeholder renders to when its value is missing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NullPolicy {
+    /// Fails the rendering with the list of entities without value.
+    Error,
+    /// Writes nothing.
+    #[default]
+    Empty,
+    /// Writes the placeholder as it appears in the template.
+    KeepPlaceholder,
+    /// Writes the fallback value declared on the template for the entity, failing when none is declared.
+    Fallback,
+}
+
+/// Options used when rendering a `Template`.
+#[derive(Debug, Clone, Default)]
+pub struct RenderContext<'a> {
+    null_policy: NullPolicy,
+    instance_objects: Option<&'a InstanceObjectCollection>,
+}
+
+impl<'a> RenderContext<'a> {
+    /// Creates a context with the default options.
+    pub fn new() -> Self {
+        RenderContext::default()
+    }
+
+    /// Sets the policy applied to missing values.
+    pub fn with_null_policy(mut self, null_policy: NullPolicy) -> Self {
+        self.null_policy = null_policy;
+        self
+    }
+
+    /// Sets the instances referenced instances are looked up in, references rendering as missing values without them.
+    pub fn with_instance_objects(mut self, instance_objects: &'a InstanceObjectCollection) -> Self {
+        self.instance_objects = Some(instance_objects);
+        self
+    }
+
+    /// Returns the instances referenced instances are looked up in.
+    pub fn get_instance_objects(&self) -> Option<&'a InstanceObjectCollection> {
+        self.instance_objects
+    }
+
+    /// Returns the policy applied to missing values.
+    pub fn get_null_policy(&self) -> NullPolicy {
+        self.null_policy
+    }
+}
@@ -0,0 +1,170 @@
+//! # EscapeMode
+//!
+//! The `EscapeMode` decides how substituted values are escaped before being written into the output,
+//! so that a value cannot break out of the context it is injected in.
+//!
+//! A mode is selected per template through `TemplateBuilder::with_escape_mode`, and can be overridden per
+//! placeholder with the `escape:MODE` filter or disabled with the `raw` filter.
+
+/// Output context substituted values are escaped for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscapeMode {
+    /// Values are written as they are.
+    #[default]
+    Raw,
+    /// Escapes `&`, `<`, `>`, `"` and `'` as HTML entities.
+    Html,
+    /// Escapes the value for use inside a JSON string literal, without adding the surrounding quotes.
+    ///
+    /// `<`, `>`, `/` and the U+2028 and U+2029 line separators are escaped too, so that the literal can neither
+    /// close an HTML `<script>` element nor end a JavaScript line.
+    Json,
+    /// Quotes the value as a CSV field when it contains a separator, a quote or a line break, or starts or ends
+    /// with whitespace.
+    ///
+    /// Values starting with `=`, `+`, `-`, `@`, a tab or a carriage return, numbers aside, are prefixed with `'`
+    /// so that spreadsheets do not read them as formulas.
+    Csv,
+    /// Single-quotes the value as one POSIX shell word.
+    Shell,
+}
+
+impl EscapeMode {
+    /// Parses a mode name as used in the `escape:MODE` filter.
+    pub fn parse(name: &str) -> Option<EscapeMode> {
+        match name {
+            "raw" => Some(EscapeMode::Raw),
+            "html" => Some(EscapeMode::Html),
+            "json" => Some(EscapeMode::Json),
+            "csv" => Some(EscapeMode::Csv),
+            "shell" => Some(EscapeMode::Shell),
+            _ => None,
+        }
+    }
+
+    /// Returns the mode name as used in the `escape:MODE` filter.
+    pub fn get_name(&self) -> &str {
+        match &self {
+            EscapeMode::Raw => "raw",
+            EscapeMode::Html => "html",
+            EscapeMode::Json => "json",
+            EscapeMode::Csv => "csv",
+            EscapeMode::Shell => "shell",
+        }
+    }
+
+    /// Escapes a value for the output context.
+    pub fn escape(&self, value: &str) -> String {
+        match &self {
+            EscapeMode::Raw => value.to_string(),
+            EscapeMode::Html => Self::escape_html(value),
+            EscapeMode::Json => Self::escape_json(value),
+            EscapeMode::Csv => Self::escape_csv(value),
+            EscapeMode::Shell => format!("'{}'", value.replace('\'', r"'\''")),
+        }
+    }
+
+    fn escape_html(value: &str) -> String {
+        value.chars().fold(String::with_capacity(value.len()), |mut output, c| {
+            match c {
+                '&' => output.push_str("&amp;"),
+                '<' => output.push_str("&lt;"),
+                '>' => output.push_str("&gt;"),
+                '"' => output.push_str("&quot;"),
+                '\'' => output.push_str("&#x27;"),
+                c => output.push(c),
+            }
+            output
+        })
+    }
+
+    fn escape_json(value: &str) -> String {
+        value.chars().fold(String::with_capacity(value.len()), |mut output, c| {
+            match c {
+                '"' => output.push_str("\\\""),
+                '\\' => output.push_str("\\\\"),
+                '\n' => output.push_str("\\n"),
+                '\r' => output.push_str("\\r"),
+                '\t' => output.push_str("\\t"),
+                '\u{08}' => output.push_str("\\b"),
+                '\u{0c}' => output.push_str("\\f"),
+                '<' => output.push_str("\\u003c"),
+                '>' => output.push_str("\\u003e"),
+                '/' => output.push_str("\\/"),
+                '\u{2028}' => output.push_str("\\u2028"),
+                '\u{2029}' => output.push_str("\\u2029"),
+                c if c < '\u{20}' => output.push_str(&format!("\\u{:04x}", c as u32)),
+                c => output.push(c),
+            }
+            output
+        })
+    }
+
+    fn escape_csv(value: &str) -> String {
+        let is_formula = value.starts_with(['=', '+', '-', '@', '\t', '\r']) && value.parse::<f64>().is_err();
+        let value = match is_formula {
+            true => format!("'{value}"),
+            false => value.to_string(),
+        };
+        let is_padded = value.trim() != value;
+        match is_padded || value.contains([',', '"', '\n', '\r']) {
+            true => format!("\"{}\"", value.replace('"', "\"\"")),
+            false => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(EscapeMode::parse("html"), Some(EscapeMode::Html));
+        assert_eq!(EscapeMode::parse("xml"), None);
+        assert_eq!(EscapeMode::parse(EscapeMode::Shell.get_name()), Some(EscapeMode::Shell));
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            EscapeMode::Html.escape(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#x27;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_escape_json() {
+        assert_eq!(
+            EscapeMode::Json.escape("say \"hi\"\\\n\u{01}"),
+            r#"say \"hi\"\\\n\u0001"#
+        );
+        assert_eq!(
+            EscapeMode::Json.escape("</script>\u{2028}\u{2029}"),
+            r#"\u003c\/script\u003e\u2028\u2029"#
+        );
+    }
+
+    #[test]
+    fn test_escape_csv() {
+        assert_eq!(EscapeMode::Csv.escape("Paris"), "Paris");
+        assert_eq!(EscapeMode::Csv.escape("Paris, France"), "\"Paris, France\"");
+        assert_eq!(EscapeMode::Csv.escape("the \"best\""), "\"the \"\"best\"\"\"");
+        assert_eq!(EscapeMode::Csv.escape(" Paris "), "\" Paris \"");
+        assert_eq!(EscapeMode::Csv.escape("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(EscapeMode::Csv.escape("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(EscapeMode::Csv.escape("-42.5"), "-42.5");
+    }
+
+    #[test]
+    fn test_escape_shell() {
+        assert_eq!(EscapeMode::Shell.escape("rm -rf /"), "'rm -rf /'");
+        assert_eq!(EscapeMode::Shell.escape("it's; ls"), r"'it'\''s; ls'");
+        assert_eq!(EscapeMode::Shell.escape(""), "''");
+    }
+
+    #[test]
+    fn test_escape_raw() {
+        assert_eq!(EscapeMode::Raw.escape("<b>"), "<b>");
+    }
+}
@@ -0,0 +1,447 @@
+//! # TemplateFilter
+//!
+//! Filters transform the value of a placeholder before it is written, e.g. `[@name|upper]` or `[@prize|pad:6]`.
+//! They are chained from left to right, each one receiving the output of the previous one.
+//!
+//! ## Built-in filters:
+//! - `upper`, `lower`, `trim`: change the case or strip the surrounding whitespace of a `Text` value.
+//! - `truncate:N`: keeps the first `N` characters of a `Text` value.
+//! - `default:VALUE`: replaces a missing value with `VALUE`, keeping the type of the value when `VALUE` parses as it
+//!   and turning the value into `Text` otherwise. `VALUE` cannot contain `|`, `[` or `]`, which end the filter
+//!   or the placeholder.
+//! - `pad:N`: left-pads an integer value with zeros up to `N` digits.
+//! - `number`: formats an integer, `F64` or `Decimal` value with thousands separators.
+//! - `currency`: formats a `Money` value with its currency symbol or code, e.g. `€1,234.50`.
+//! - `date:FORMAT`: formats a `Date`, `DateTime` or `Time` value, or an ISO 8601 date held in a `Text` value,
+//!   using a `strftime` format.
+//! - `duration:STYLE`: formats a `Duration` value as a `clock` time (`2:05:30`), an `iso` duration (`PT2H5M30S`)
+//!   or in `words` (`2h 5m 30s`).
+//! - `label`: writes the label of an `Enum` value instead of its key.
+//! - `host`: writes the lowercased host of a `Url` value, e.g. `www.example.com`.
+//! - `mailto`: writes an `Email` value as a `mailto:` link.
+//! - `tel`: writes a `Phone` value as a `tel:` link.
+//! - `escape:MODE`: escapes the value for the given `EscapeMode` instead of the template one.
+//! - `raw`: writes the value without escaping.
+//!
+//! `escape` and `raw` leave the value untouched and only choose how it is written; the last one of a chain wins.
+//!
+//! Filters are type-checked against the `MetaAttributes` of the referenced entity when the template is built.
+
+use std::fmt::Write;
+
+use chrono::{format::{Item, StrftimeItems}, DateTime, NaiveDate, NaiveDateTime};
+
+use crate::core::{errors::TemplateError, instance::instance_entities::InstanceAttributes, meta::meta_entity::MetaAttributes, model::Decimal};
+
+use super::template_escape::EscapeMode;
+
+/// How the `duration` filter writes a `Duration`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DurationStyle {
+    Clock,
+    Iso,
+    Words,
+}
+
+/// A filter applied to a placeholder value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Upper,
+    Lower,
+    Trim,
+    Truncate(usize),
+    /// The fallback of `default`, which cannot contain `|`, `[` or `]` as they end the filter or the placeholder.
+    Default(String),
+    Pad(usize),
+    Number,
+    Currency,
+    Date(String),
+    Duration(DurationStyle),
+    Label,
+    Host,
+    Mailto,
+    Tel,
+    Escape(EscapeMode),
+    Raw,
+}
+
+impl Filter {
+    /// Parses a single filter expression such as `upper` or `pad:6`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem when the filter is unknown or its argument is invalid.
+    pub fn parse(expression: &str) -> Result<Filter, String> {
+        let (name, argument) = match expression.split_once(':') {
+            Some((name, argument)) => (name.trim(), Some(argument.trim())),
+            None => (expression.trim(), None),
+        };
+
+        match (name, argument) {
+            ("upper", None) => Ok(Filter::Upper),
+            ("lower", None) => Ok(Filter::Lower),
+            ("trim", None) => Ok(Filter::Trim),
+            ("number", None) => Ok(Filter::Number),
+            ("currency", None) => Ok(Filter::Currency),
+            ("label", None) => Ok(Filter::Label),
+            ("host", None) => Ok(Filter::Host),
+            ("mailto", None) => Ok(Filter::Mailto),
+            ("tel", None) => Ok(Filter::Tel),
+            ("raw", None) => Ok(Filter::Raw),
+            ("escape", Some(mode)) => EscapeMode::parse(mode)
+                .map(Filter::Escape)
+                .ok_or_else(|| format!("unknown escape mode '{mode}'")),
+            ("truncate", Some(length)) => Self::parse_length(name, length).map(Filter::Truncate),
+            ("pad", Some(width)) => Self::parse_length(name, width).map(Filter::Pad),
+            ("default", Some(value)) => Ok(Filter::Default(value.to_string())),
+            ("date", Some(format)) if Self::is_valid_date_format(format) => Ok(Filter::Date(format.to_string())),
+            ("date", Some(format)) => Err(format!("invalid date format '{format}'")),
+            ("duration", Some("clock")) => Ok(Filter::Duration(DurationStyle::Clock)),
+            ("duration", Some("iso")) => Ok(Filter::Duration(DurationStyle::Iso)),
+            ("duration", Some("words")) => Ok(Filter::Duration(DurationStyle::Words)),
+            ("duration", Some(style)) => Err(format!("unknown duration style '{style}'")),
+            ("upper" | "lower" | "trim" | "number" | "currency" | "label" | "host" | "mailto" | "tel" | "raw", Some(_)) => Err(format!("filter '{name}' takes no argument")),
+            ("truncate" | "pad" | "default" | "date" | "duration" | "escape", None) => Err(format!("filter '{name}' requires an argument")),
+            _ => Err(format!("unknown filter '{name}'")),
+        }
+    }
+
+    /// Returns the name of the filter as written in templates.
+    pub fn get_name(&self) -> &str {
+        match &self {
+            Filter::Upper => "upper",
+            Filter::Lower => "lower",
+            Filter::Trim => "trim",
+            Filter::Truncate(_) => "truncate",
+            Filter::Default(_) => "default",
+            Filter::Pad(_) => "pad",
+            Filter::Number => "number",
+            Filter::Currency => "currency",
+            Filter::Date(_) => "date",
+            Filter::Duration(_) => "duration",
+            Filter::Label => "label",
+            Filter::Host => "host",
+            Filter::Mailto => "mailto",
+            Filter::Tel => "tel",
+            Filter::Escape(_) => "escape",
+            Filter::Raw => "raw",
+        }
+    }
+
+    /// Returns the type produced by the filter for the given input type, or `None` if the input is not supported.
+    pub fn output_type(&self, input: &MetaAttributes) -> Option<MetaAttributes> {
+        match (&self, input) {
+            (Filter::Upper | Filter::Lower | Filter::Trim | Filter::Truncate(_) | Filter::Date(_), MetaAttributes::Text) => {
+                Some(MetaAttributes::Text)
+            }
+            (Filter::Pad(_) | Filter::Number, input) if input.is_integer() => Some(MetaAttributes::Text),
+            (Filter::Number, MetaAttributes::Decimal(_) | MetaAttributes::F64) => Some(MetaAttributes::Text),
+            (Filter::Currency, MetaAttributes::Money(_)) => Some(MetaAttributes::Text),
+            (Filter::Date(_), input) if input.is_temporal() => Some(MetaAttributes::Text),
+            (Filter::Duration(_), MetaAttributes::Duration) => Some(MetaAttributes::Text),
+            (Filter::Label, MetaAttributes::Enum(_)) => Some(MetaAttributes::Text),
+            (Filter::Host, MetaAttributes::Url) => Some(MetaAttributes::Text),
+            (Filter::Mailto, MetaAttributes::Email) => Some(MetaAttributes::Text),
+            (Filter::Tel, MetaAttributes::Phone) => Some(MetaAttributes::Text),
+            (Filter::Default(fallback), input) => match Self::parse_fallback(fallback, &input.insert_none_for_type()) {
+                Some(_) => Some(input.clone()),
+                None => Some(MetaAttributes::Text),
+            },
+            (Filter::Escape(_) | Filter::Raw, input) => Some(input.clone()),
+            _ => None,
+        }
+    }
+
+    /// Applies the filter to a value.
+    ///
+    /// Missing values go through untouched, except for `default` which replaces them. `default` writes values
+    /// as `Text` when its fallback does not parse as their type, as announced by `output_type`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::FilterFailed` when the value cannot be processed, e.g. a text that is not a date.
+    pub fn apply(&self, value: InstanceAttributes) -> Result<InstanceAttributes, TemplateError> {
+        let text = |output: String| Ok(InstanceAttributes::Text(Some(output)));
+
+        match (&self, &value, value.as_integer()) {
+            (Filter::Default(fallback), _, _) => match (Self::parse_fallback(fallback, &value), value.is_null()) {
+                (Some(typed), true) => Ok(typed),
+                (Some(_), false) => Ok(value),
+                (None, true) => text(fallback.to_string()),
+                (None, false) => text(value.to_string()),
+            },
+            (Filter::Upper, InstanceAttributes::Text(Some(t)), _) => text(t.to_uppercase()),
+            (Filter::Lower, InstanceAttributes::Text(Some(t)), _) => text(t.to_lowercase()),
+            (Filter::Trim, InstanceAttributes::Text(Some(t)), _) => text(t.trim().to_string()),
+            (Filter::Truncate(length), InstanceAttributes::Text(Some(t)), _) => text(t.chars().take(*length).collect()),
+            (Filter::Pad(width), _, Some(integer)) => text(Self::pad(integer, *width)),
+            (Filter::Number, _, Some(integer)) => text(Decimal::new(integer, 0).to_grouped_string()),
+            (Filter::Number, InstanceAttributes::Decimal(Some(d)), _) => text(d.to_grouped_string()),
+            (Filter::Number, InstanceAttributes::F64(Some(f)), _) => text(Self::group_float(*f)),
+            (Filter::Currency, InstanceAttributes::Money(Some(m)), _) => text(m.to_currency_string()),
+            (Filter::Date(format), InstanceAttributes::Text(Some(t)), _) => Self::format_date(t, format)
+                .map(|output| InstanceAttributes::Text(Some(output)))
+                .ok_or_else(|| TemplateError::FilterFailed {
+                    filter: self.get_name().to_string(),
+                    value: t.to_string(),
+                }),
+            (Filter::Date(format), InstanceAttributes::Date(Some(date)), _) => self.write_formatted(&value, date.format(format)),
+            (Filter::Date(format), InstanceAttributes::DateTime(Some(date_time)), _) => {
+                self.write_formatted(&value, date_time.format(format))
+            }
+            (Filter::Date(format), InstanceAttributes::Time(Some(time)), _) => self.write_formatted(&value, time.format(format)),
+            (Filter::Label, InstanceAttributes::Enum(Some(choice)), _) => text(choice.get_label().to_string()),
+            (Filter::Host, InstanceAttributes::Url(Some(url)), _) => text(url.get_host()),
+            (Filter::Mailto, InstanceAttributes::Email(Some(email)), _) => text(email.to_mailto()),
+            (Filter::Tel, InstanceAttributes::Phone(Some(phone)), _) => text(phone.to_tel()),
+            (Filter::Duration(style), InstanceAttributes::Duration(Some(duration)), _) => text(match style {
+                DurationStyle::Clock => duration.to_string(),
+                DurationStyle::Iso => duration.to_iso_string(),
+                DurationStyle::Words => duration.to_words_string(),
+            }),
+            (Filter::Escape(_) | Filter::Raw, _, _) => Ok(value),
+            (_, value, _) if value.is_null() => Ok(value.clone()),
+            (_, value, _) => Err(TemplateError::FilterFailed {
+                filter: self.get_name().to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    /// Returns the escape mode chosen by the filter, if it is an `escape` or `raw` filter.
+    pub fn get_escape_mode(&self) -> Option<EscapeMode> {
+        match &self {
+            Filter::Escape(mode) => Some(*mode),
+            Filter::Raw => Some(EscapeMode::Raw),
+            _ => None,
+        }
+    }
+
+    fn parse_length(name: &str, argument: &str) -> Result<usize, String> {
+        argument
+            .parse::<usize>()
+            .ok()
+            .filter(|length| *length > 0)
+            .ok_or_else(|| format!("filter '{name}' expects a positive integer, got '{argument}'"))
+    }
+
+    /// Parses the fallback of `default` as the type of the value, `None` meaning that it does not parse and that
+    /// the value is written as `Text`.
+    ///
+    /// Only the types that can be read back from the value alone keep their type: `Decimal`, `Money`, `Enum`,
+    /// collections and objects always turn into `Text`, and dates and times are parsed with their default format.
+    fn parse_fallback(fallback: &str, value: &InstanceAttributes) -> Option<InstanceAttributes> {
+        let input = Some(fallback);
+        match value {
+            InstanceAttributes::Text(_) => InstanceAttributes::parse_text(input).ok(),
+            InstanceAttributes::Bool(_) => InstanceAttributes::parse_bool(input).ok(),
+            InstanceAttributes::I16(_) => InstanceAttributes::parse_i16(input).ok(),
+            InstanceAttributes::I32(_) => InstanceAttributes::parse_i32(input).ok(),
+            InstanceAttributes::I64(_) => InstanceAttributes::parse_i64(input).ok(),
+            InstanceAttributes::U64(_) => InstanceAttributes::parse_u64(input).ok(),
+            InstanceAttributes::F64(_) => InstanceAttributes::parse_f64(input).ok(),
+            InstanceAttributes::Date(_) => InstanceAttributes::parse_date(input, None).ok(),
+            InstanceAttributes::DateTime(_) => InstanceAttributes::parse_datetime(input, None).ok(),
+            InstanceAttributes::Time(_) => InstanceAttributes::parse_time(input, None).ok(),
+            InstanceAttributes::Duration(_) => InstanceAttributes::parse_duration(input).ok(),
+            InstanceAttributes::Url(_) => InstanceAttributes::parse_url(input).ok(),
+            InstanceAttributes::Email(_) => InstanceAttributes::parse_email(input).ok(),
+            InstanceAttributes::Phone(_) => InstanceAttributes::parse_phone(input).ok(),
+            InstanceAttributes::Slug(_) => InstanceAttributes::parse_slug(input).ok(),
+            InstanceAttributes::Reference(_) => InstanceAttributes::parse_reference(input).ok(),
+            InstanceAttributes::Decimal(_)
+            | InstanceAttributes::Money(_)
+            | InstanceAttributes::Enum(_)
+            | InstanceAttributes::Object(_)
+            | InstanceAttributes::List(_)
+            | InstanceAttributes::Set(_) => None,
+        }
+    }
+
+    fn is_valid_date_format(format: &str) -> bool {
+        !format.is_empty() && StrftimeItems::new(format).all(|item| !matches!(item, Item::Error))
+    }
+
+    /// Formats a float with `,` between groups of three integer digits, keeping its shortest decimal form.
+    fn group_float(value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        let written = value.abs().to_string();
+        let (integer, fraction) = match written.split_once('.') {
+            Some((integer, fraction)) => (integer, format!(".{fraction}")),
+            None => (written.as_str(), String::new()),
+        };
+        let grouped = integer
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|chunk| std::str::from_utf8(chunk).expect("digits are ASCII"))
+            .collect::<Vec<&str>>()
+            .join(",");
+        let sign = if value < 0.0 { "-" } else { "" };
+        format!("{sign}{grouped}{fraction}")
+    }
+
+    fn pad(value: i128, width: usize) -> String {
+        match value < 0 {
+            true => format!("-{:0width$}", value.unsigned_abs(), width = width.saturating_sub(1)),
+            false => format!("{:0width$}", value, width = width),
+        }
+    }
+
+    /// Writes a formatted date or time, failing when the format asks for fields the value does not have,
+    /// such as `%Y` for a `Time`.
+    fn write_formatted(&self, value: &InstanceAttributes, formatted: impl std::fmt::Display) -> Result<InstanceAttributes, TemplateError> {
+        let mut output = String::new();
+        write!(output, "{}", formatted)
+            .map(|_| InstanceAttributes::Text(Some(output)))
+            .map_err(|_| TemplateError::FilterFailed {
+                filter: self.get_name().to_string(),
+                value: value.to_string(),
+            })
+    }
+
+    /// Parses an ISO 8601 date, date-time or RFC 3339 timestamp and formats it.
+    fn format_date(value: &str, format: &str) -> Option<String> {
+        let mut output = String::new();
+        let written = if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            write!(output, "{}", date.format(format))
+        } else if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+            write!(output, "{}", date_time.format(format))
+        } else if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+            write!(output, "{}", date_time.format(format))
+        } else {
+            return None;
+        };
+        written.ok().map(|_| output)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::core::model::{Currency, Money};
+
+    use super::*;
+
+    fn text(value: &str) -> InstanceAttributes {
+        InstanceAttributes::Text(Some(value.to_string()))
+    }
+
+    #[test]
+    fn test_parse_filters() {
+        assert_eq!(Filter::parse("upper"), Ok(Filter::Upper));
+        assert_eq!(Filter::parse(" pad : 6 "), Ok(Filter::Pad(6)));
+        assert_eq!(Filter::parse("default:To be announced"), Ok(Filter::Default("To be announced".to_string())));
+        assert_eq!(Filter::parse("date:%H:%M"), Ok(Filter::Date("%H:%M".to_string())));
+        assert!(Filter::parse("pad").is_err());
+        assert!(Filter::parse("pad:-2").is_err());
+        assert!(Filter::parse("pad:0").is_err());
+        assert!(Filter::parse("truncate:0").is_err());
+        assert!(Filter::parse("upper:1").is_err());
+        assert!(Filter::parse("date:%Q").is_err());
+        assert_eq!(Filter::parse("escape:json"), Ok(Filter::Escape(EscapeMode::Json)));
+        assert_eq!(Filter::parse("raw"), Ok(Filter::Raw));
+        assert!(Filter::parse("escape:xml").is_err());
+        assert!(Filter::parse("shout").is_err());
+    }
+
+    #[test]
+    fn test_output_type() {
+        assert!(matches!(Filter::Upper.output_type(&MetaAttributes::Text), Some(MetaAttributes::Text)));
+        assert!(Filter::Upper.output_type(&MetaAttributes::I16).is_none());
+        assert!(matches!(Filter::Pad(6).output_type(&MetaAttributes::I16), Some(MetaAttributes::Text)));
+        assert!(Filter::Number.output_type(&MetaAttributes::Text).is_none());
+        assert!(matches!(Filter::Number.output_type(&MetaAttributes::I64), Some(MetaAttributes::Text)));
+        assert!(matches!(Filter::Number.output_type(&MetaAttributes::F64), Some(MetaAttributes::Text)));
+        assert!(matches!(Filter::Default("n/a".to_string()).output_type(&MetaAttributes::I16), Some(MetaAttributes::Text)));
+        assert!(matches!(Filter::Default("0".to_string()).output_type(&MetaAttributes::I16), Some(MetaAttributes::I16)));
+        assert!(matches!(Filter::Default("0".to_string()).output_type(&MetaAttributes::Decimal(2)), Some(MetaAttributes::Text)));
+    }
+
+    #[test]
+    fn test_apply_text_filters() {
+        assert_eq!(Filter::Upper.apply(text("Paris")), Ok(text("PARIS")));
+        assert_eq!(Filter::Lower.apply(text("Paris")), Ok(text("paris")));
+        assert_eq!(Filter::Trim.apply(text("  Paris ")), Ok(text("Paris")));
+        assert_eq!(Filter::Truncate(3).apply(text("Marathon")), Ok(text("Mar")));
+        assert_eq!(Filter::Upper.apply(InstanceAttributes::Text(None)), Ok(InstanceAttributes::Text(None)));
+    }
+
+    #[test]
+    fn test_apply_number_filters() {
+        assert_eq!(Filter::Pad(6).apply(InstanceAttributes::I16(Some(2030))), Ok(text("002030")));
+        assert_eq!(Filter::Pad(4).apply(InstanceAttributes::I16(Some(-7))), Ok(text("-007")));
+        assert_eq!(Filter::Number.apply(InstanceAttributes::I16(Some(32000))), Ok(text("32,000")));
+        assert_eq!(Filter::Number.apply(InstanceAttributes::I16(Some(-999))), Ok(text("-999")));
+        assert_eq!(Filter::Number.apply(InstanceAttributes::U64(Some(u64::MAX))), Ok(text("18,446,744,073,709,551,615")));
+        assert_eq!(Filter::Pad(8).apply(InstanceAttributes::I32(Some(40000))), Ok(text("00040000")));
+        assert_eq!(Filter::Number.apply(InstanceAttributes::F64(Some(1234567.25))), Ok(text("1,234,567.25")));
+        assert_eq!(Filter::Number.apply(InstanceAttributes::F64(Some(-42195.0))), Ok(text("-42,195")));
+        assert_eq!(Filter::Number.apply(InstanceAttributes::F64(Some(0.5))), Ok(text("0.5")));
+    }
+
+    #[test]
+    fn test_apply_decimal_filters() {
+        let prize = Money::parse("-1234.5", Currency::from_code("EUR").unwrap()).unwrap();
+        assert_eq!(Filter::Currency.apply(InstanceAttributes::Money(Some(prize))), Ok(text("-€1,234.50")));
+        assert_eq!(Filter::Number.apply(InstanceAttributes::Decimal(Some(Decimal::new(123456, 1)))), Ok(text("12,345.6")));
+        assert!(Filter::Currency.output_type(&MetaAttributes::Decimal(2)).is_none());
+        assert_eq!(Filter::parse("currency"), Ok(Filter::Currency));
+    }
+
+    #[test]
+    fn test_apply_temporal_filters() {
+        let start = MetaAttributes::DateTime(None).parse_attribute(Some("2024-04-07T08:15:00+02:00")).unwrap();
+        assert_eq!(Filter::Date("%d/%m/%Y %H:%M".to_string()).apply(start), Ok(text("07/04/2024 08:15")));
+        let time = MetaAttributes::Time(None).parse_attribute(Some("08:15")).unwrap();
+        assert_eq!(Filter::Date("%Hh%M".to_string()).apply(time.clone()), Ok(text("08h15")));
+        assert!(Filter::Date("%Y".to_string()).apply(time).is_err());
+
+        let finish = MetaAttributes::Duration.parse_attribute(Some("2:05:30")).unwrap();
+        assert_eq!(Filter::parse("duration:words"), Ok(Filter::Duration(DurationStyle::Words)));
+        assert_eq!(Filter::Duration(DurationStyle::Iso).apply(finish), Ok(text("PT2H5M30S")));
+        assert!(Filter::parse("duration:days").is_err());
+        assert!(Filter::Duration(DurationStyle::Clock).output_type(&MetaAttributes::Time(None)).is_none());
+        assert!(matches!(Filter::Date("%d".to_string()).output_type(&MetaAttributes::Date(None)), Some(MetaAttributes::Text)));
+    }
+
+    #[test]
+    fn test_apply_semantic_filters() {
+        let ref_link = MetaAttributes::Url.parse_attribute(Some("https://Results.example.com/2024")).unwrap();
+        assert_eq!(Filter::Host.apply(ref_link), Ok(text("results.example.com")));
+        let contact = MetaAttributes::Email.parse_attribute(Some("contact@example.com")).unwrap();
+        assert_eq!(Filter::Mailto.apply(contact), Ok(text("mailto:contact@example.com")));
+        let phone = MetaAttributes::Phone.parse_attribute(Some("+33 1 23 45 67 89")).unwrap();
+        assert_eq!(Filter::Tel.apply(phone), Ok(text("tel:+33123456789")));
+        assert!(Filter::Host.output_type(&MetaAttributes::Text).is_none());
+        assert!(Filter::Upper.output_type(&MetaAttributes::Slug).is_none());
+        assert!(Filter::parse("mailto:x").is_err());
+    }
+
+    #[test]
+    fn test_apply_default() {
+        let filter = Filter::Default("TBA".to_string());
+        assert_eq!(filter.apply(InstanceAttributes::I16(None)), Ok(text("TBA")));
+        assert_eq!(filter.apply(InstanceAttributes::I16(Some(3))), Ok(text("3")));
+
+        let filter = Filter::Default("0".to_string());
+        assert_eq!(filter.apply(InstanceAttributes::I16(None)), Ok(InstanceAttributes::I16(Some(0))));
+        assert_eq!(filter.apply(InstanceAttributes::I16(Some(3))), Ok(InstanceAttributes::I16(Some(3))));
+    }
+
+    #[test]
+    fn test_apply_date() {
+        let filter = Filter::Date("%d/%m/%Y".to_string());
+        assert_eq!(filter.apply(text("2024-04-07")), Ok(text("07/04/2024")));
+        assert_eq!(filter.apply(text("2024-04-07T08:15:00+02:00")), Ok(text("07/04/2024")));
+        assert_eq!(
+            filter.apply(text("next sunday")),
+            Err(TemplateError::FilterFailed {
+                filter: "date".to_string(),
+                value: "next sunday".to_string()
+            })
+        );
+        assert!(Filter::Date("%H:%M".to_string()).apply(text("2024-04-07")).is_err());
+    }
+}
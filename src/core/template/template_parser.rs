@@ -0,0 +1,740 @@
+//! # TemplateParser
+//!
+//! The `TemplateParser` turns the raw string held by a `TemplateContent` into a tree of `TemplateNode`s.
+//!
+//! ## Responsibilities:
+//! - Splits the content into literal spans, placeholder nodes and directive blocks.
+//! - Keeps literal spans byte-for-byte, so whitespace, newlines and punctuation survive rendering.
+//! - Rejects malformed placeholders and unbalanced blocks instead of silently emitting them.
+//!
+//! ## Directives:
+//! - `[#if @entity]` ... `[#else]` ... `[#endif]`: renders the first branch when the entity value is truthy,
+//!   the optional `else` branch otherwise.
+//! - `[#block name]` ... `[#endblock]`: a named section a child template can override when it extends this one.
+//! - `[#for item in @entity separator=", "]` ... `[#endfor]`: renders the body once per element of a collection
+//!   entity, the optional separator being written between elements. The body references the element as `[@item]`
+//!   and the loop state as `[@loop.index]` (from 1), `[@loop.first]` and `[@loop.last]`.
+//!
+//! ## References:
+//! - `[@entity.field]`: dotted references read the entities of nested objects, in placeholders, conditions and loops.
+//!
+//! ## Partials:
+//! - `[>name]`: includes another template, looked up by id or name in a `TemplateCollection`.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::errors::TemplateError;
+
+use super::template_content::TemplateContent;
+use super::template_filter::Filter;
+
+/// A single node of a parsed template.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateNode {
+    /// Raw text copied verbatim to the output.
+    Literal(String),
+    /// A `[@reference]` placeholder substituted at render time.
+    Placeholder(Placeholder),
+    /// An `[#if @reference]` block.
+    Conditional(Conditional),
+    /// A `[>key]` partial, where the key is the id or the name of another template.
+    Include(String),
+    /// A `[#block name]` section, overridable by child templates.
+    Block(Block),
+    /// A `[#for item in @reference]` block.
+    Loop(Loop),
+}
+
+/// A placeholder referencing an entity of the parent `MetaObject`, optionally followed by filters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    reference: String,
+    filters: Vec<Filter>,
+    source: String,
+}
+
+impl Placeholder {
+    /// Returns the name of the referenced entity.
+    pub fn get_reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// Returns the filters applied to the value, in order.
+    pub fn get_filters(&self) -> &[Filter] {
+        &self.filters
+    }
+
+    /// Returns the placeholder exactly as written in the template.
+    pub fn get_source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// A conditional block choosing between two branches based on an entity value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditional {
+    reference: String,
+    then_branch: Vec<TemplateNode>,
+    else_branch: Vec<TemplateNode>,
+}
+
+impl Conditional {
+    /// Returns the name of the entity the condition is evaluated on.
+    pub fn get_reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// Returns the nodes rendered when the condition holds.
+    pub fn get_then_branch(&self) -> &[TemplateNode] {
+        &self.then_branch
+    }
+
+    /// Returns the nodes rendered when the condition does not hold.
+    pub fn get_else_branch(&self) -> &[TemplateNode] {
+        &self.else_branch
+    }
+}
+
+/// A named section of a layout template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    name: String,
+    body: Vec<TemplateNode>,
+}
+
+impl Block {
+    /// Returns the name of the block.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the nodes rendered for the block.
+    pub fn get_body(&self) -> &[TemplateNode] {
+        &self.body
+    }
+}
+
+/// A loop rendering its body once per element of a collection entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loop {
+    variable: String,
+    reference: String,
+    separator: String,
+    body: Vec<TemplateNode>,
+}
+
+impl Loop {
+    /// Name of the loop state variable, read as `[@loop.index]`, `[@loop.first]` and `[@loop.last]`.
+    pub const STATE: &'static str = "loop";
+
+    /// Returns the name the current element is bound to in the body.
+    pub fn get_variable(&self) -> &str {
+        &self.variable
+    }
+
+    /// Returns the name of the collection the loop iterates over.
+    pub fn get_reference(&self) -> &str {
+        &self.reference
+    }
+
+    /// Returns the text written between two elements.
+    pub fn get_separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Returns the nodes rendered for each element.
+    pub fn get_body(&self) -> &[TemplateNode] {
+        &self.body
+    }
+
+    /// Returns whether the reference is bound by the loop, either to its element or to its state.
+    pub fn binds(&self, reference: &str) -> bool {
+        let root = TemplateParser::root_reference(reference);
+        root == self.variable || root == Self::STATE
+    }
+}
+
+/// Flat token produced by scanning the raw content.
+enum Token<'a> {
+    Literal(&'a str),
+    Placeholder(Placeholder),
+    Include(String),
+    Directive {
+        keyword: &'a str,
+        argument: &'a str,
+        position: usize,
+    },
+}
+
+/// Closing directive met by `parse_block`, with its position in the content.
+type Closing<'a> = Option<(&'a str, usize)>;
+
+/// Parser producing `TemplateNode`s from raw template content.
+pub struct TemplateParser;
+
+impl TemplateParser {
+    /// Parses the raw content into a tree of nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::InvalidSyntax` when a placeholder is empty or never closed,
+    /// when a directive is unknown or when blocks are unbalanced.
+    pub fn parse(content: &str) -> Result<Vec<TemplateNode>, TemplateError> {
+        let tokens = Self::tokenize(content)?;
+        Self::check_unique_blocks(&tokens)?;
+        let mut tokens = tokens.into_iter();
+
+        match Self::parse_block(&mut tokens)? {
+            (nodes, None) => Ok(nodes),
+            (_, Some((keyword, position))) => Err(Self::syntax_error(
+                position,
+                &format!("unexpected [#{keyword}]"),
+            )),
+        }
+    }
+
+    /// Visits every node, depth first, including those nested in blocks.
+    pub fn walk<'a>(nodes: &'a [TemplateNode], visit: &mut impl FnMut(&'a TemplateNode)) {
+        Self::walk_scoped(nodes, &mut Vec::new(), &mut |node, _| visit(node));
+    }
+
+    /// Visits every node, depth first, along with the loops enclosing it, from the outermost one.
+    pub fn walk_scoped<'a>(
+        nodes: &'a [TemplateNode],
+        scope: &mut Vec<&'a Loop>,
+        visit: &mut impl FnMut(&'a TemplateNode, &[&'a Loop]),
+    ) {
+        nodes.iter().for_each(|node| {
+            visit(node, scope);
+            match node {
+                TemplateNode::Conditional(conditional) => {
+                    Self::walk_scoped(conditional.get_then_branch(), scope, visit);
+                    Self::walk_scoped(conditional.get_else_branch(), scope, visit);
+                }
+                TemplateNode::Block(block) => Self::walk_scoped(block.get_body(), scope, visit),
+                TemplateNode::Loop(body) => {
+                    scope.push(body);
+                    Self::walk_scoped(body.get_body(), scope, visit);
+                    scope.pop();
+                }
+                TemplateNode::Literal(_) | TemplateNode::Placeholder(_) | TemplateNode::Include(_) => {}
+            }
+        });
+    }
+
+    /// Collects the entity names referenced by the nodes, including those used in conditions and loops.
+    ///
+    /// References bound by an enclosing loop are local to it and left out.
+    pub fn collect_references(nodes: &[TemplateNode]) -> HashSet<&str> {
+        let mut references = HashSet::new();
+        Self::walk_scoped(nodes, &mut Vec::new(), &mut |node, scope| {
+            let reference = match node {
+                TemplateNode::Placeholder(placeholder) => placeholder.get_reference(),
+                TemplateNode::Conditional(conditional) => conditional.get_reference(),
+                TemplateNode::Loop(body) => body.get_reference(),
+                TemplateNode::Literal(_) | TemplateNode::Include(_) | TemplateNode::Block(_) => return,
+            };
+            if !Self::is_local(reference, scope) {
+                references.insert(reference);
+            }
+        });
+        references
+    }
+
+    /// Returns the first segment of a dotted reference, e.g. `address` for `address.city`.
+    pub fn root_reference(reference: &str) -> &str {
+        reference.split('.').next().unwrap_or(reference)
+    }
+
+    /// Returns whether the reference is bound by one of the enclosing loops.
+    pub fn is_local(reference: &str, scope: &[&Loop]) -> bool {
+        scope.iter().any(|body| body.binds(reference))
+    }
+
+    /// Collects every placeholder of the nodes, including those nested in blocks.
+    pub fn collect_placeholders(nodes: &[TemplateNode]) -> Vec<&Placeholder> {
+        let mut placeholders = Vec::new();
+        Self::walk(nodes, &mut |node| {
+            if let TemplateNode::Placeholder(placeholder) = node {
+                placeholders.push(placeholder);
+            }
+        });
+        placeholders
+    }
+
+    /// Collects the keys of the partials included by the nodes, in order of appearance.
+    pub fn collect_includes(nodes: &[TemplateNode]) -> Vec<&str> {
+        let mut includes = Vec::new();
+        Self::walk(nodes, &mut |node| {
+            if let TemplateNode::Include(key) = node {
+                includes.push(key.as_str());
+            }
+        });
+        includes
+    }
+
+    /// Collects the blocks of the nodes by name, including nested ones.
+    pub fn collect_blocks(nodes: &[TemplateNode]) -> HashMap<&str, &[TemplateNode]> {
+        let mut blocks = HashMap::new();
+        Self::walk(nodes, &mut |node| {
+            if let TemplateNode::Block(block) = node {
+                blocks.insert(block.get_name(), block.get_body());
+            }
+        });
+        blocks
+    }
+
+    /// Returns a copy of the nodes where the body of each block found in `overrides` is replaced.
+    ///
+    /// Overriding bodies may define blocks of their own, which are overridden as well.
+    pub fn override_blocks(nodes: &[TemplateNode], overrides: &HashMap<&str, &[TemplateNode]>) -> Vec<TemplateNode> {
+        nodes
+            .iter()
+            .map(|node| match node {
+                TemplateNode::Block(block) => TemplateNode::Block(Block {
+                    name: block.name.to_string(),
+                    body: Self::override_blocks(
+                        overrides.get(block.get_name()).copied().unwrap_or(block.get_body()),
+                        overrides,
+                    ),
+                }),
+                TemplateNode::Conditional(conditional) => TemplateNode::Conditional(Conditional {
+                    reference: conditional.reference.to_string(),
+                    then_branch: Self::override_blocks(conditional.get_then_branch(), overrides),
+                    else_branch: Self::override_blocks(conditional.get_else_branch(), overrides),
+                }),
+                TemplateNode::Loop(body) => TemplateNode::Loop(Loop {
+                    body: Self::override_blocks(body.get_body(), overrides),
+                    ..body.clone()
+                }),
+                node => node.clone(),
+            })
+            .collect()
+    }
+
+    /// Scans the content into literal, placeholder and directive tokens.
+    fn tokenize(content: &str) -> Result<Vec<Token<'_>>, TemplateError> {
+        let mut tokens = Vec::new();
+        let mut cursor = 0;
+
+        for caps in TemplateContent::get_pattern().captures_iter(content) {
+            let whole = caps.get(0).expect("capture 0 is always present");
+            Self::push_literal(&mut tokens, content, cursor, whole.start())?;
+
+            if let Some(body) = caps.name("reference") {
+                tokens.push(Token::Placeholder(Self::parse_placeholder(
+                    body.as_str(),
+                    whole.as_str(),
+                    whole.start(),
+                )?));
+            } else if let Some(key) = caps.name("partial") {
+                let key = key.as_str().trim();
+                if key.is_empty() || key.contains(char::is_whitespace) {
+                    return Err(Self::syntax_error(whole.start(), "invalid partial name"));
+                }
+                tokens.push(Token::Include(key.to_string()));
+            } else if let Some(directive) = caps.name("directive") {
+                let directive = directive.as_str().trim();
+                let (keyword, argument) = directive
+                    .split_once(char::is_whitespace)
+                    .unwrap_or((directive, ""));
+                tokens.push(Token::Directive {
+                    keyword,
+                    argument: argument.trim(),
+                    position: whole.start(),
+                });
+            }
+            cursor = whole.end();
+        }
+
+        Self::push_literal(&mut tokens, content, cursor, content.len())?;
+        Ok(tokens)
+    }
+
+    /// Rejects two blocks sharing the same name.
+    fn check_unique_blocks(tokens: &[Token<'_>]) -> Result<(), TemplateError> {
+        let mut names = HashSet::new();
+        tokens.iter().try_for_each(|token| match token {
+            Token::Directive { keyword: "block", argument, position } if !names.insert(*argument) => {
+                Err(Self::syntax_error(*position, &format!("duplicate block '{argument}'")))
+            }
+            _ => Ok(()),
+        })
+    }
+
+    /// Builds nodes until the tokens run out or a closing directive is met.
+    ///
+    /// The closing directive (`else`, `endif`, `endblock`, `endfor`) is returned with its position so the caller can check it.
+    fn parse_block<'a>(
+        tokens: &mut impl Iterator<Item = Token<'a>>,
+    ) -> Result<(Vec<TemplateNode>, Closing<'a>), TemplateError> {
+        let mut nodes = Vec::new();
+
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Literal(text) => nodes.push(TemplateNode::Literal(text.to_string())),
+                Token::Placeholder(placeholder) => nodes.push(TemplateNode::Placeholder(placeholder)),
+                Token::Include(key) => nodes.push(TemplateNode::Include(key)),
+                Token::Directive { keyword: "if", argument, position } => {
+                    nodes.push(TemplateNode::Conditional(Self::parse_conditional(tokens, argument, position)?));
+                }
+                Token::Directive { keyword: "block", argument, position } => {
+                    nodes.push(TemplateNode::Block(Self::parse_named_block(tokens, argument, position)?));
+                }
+                Token::Directive { keyword: "for", argument, position } => {
+                    nodes.push(TemplateNode::Loop(Self::parse_loop(tokens, argument, position)?));
+                }
+                Token::Directive { keyword: keyword @ ("else" | "endif" | "endblock" | "endfor"), position, .. } => {
+                    return Ok((nodes, Some((keyword, position))));
+                }
+                Token::Directive { keyword, position, .. } => {
+                    return Err(Self::syntax_error(position, &format!("unknown directive [#{keyword}]")));
+                }
+            }
+        }
+
+        Ok((nodes, None))
+    }
+
+    /// Parses the body of a placeholder: a reference followed by `|`-separated filters.
+    fn parse_placeholder(body: &str, source: &str, position: usize) -> Result<Placeholder, TemplateError> {
+        let mut parts = body.split('|');
+        let reference = parts.next().unwrap_or_default().trim();
+        if reference.is_empty() {
+            return Err(Self::syntax_error(position, "placeholder without reference"));
+        }
+
+        let filters = parts
+            .map(Filter::parse)
+            .collect::<Result<Vec<Filter>, String>>()
+            .map_err(|reason| Self::syntax_error(position, &reason))?;
+
+        Ok(Placeholder {
+            reference: reference.to_string(),
+            filters,
+            source: source.to_string(),
+        })
+    }
+
+    /// Parses the branches of an `[#if @reference]` block, the opening directive being already consumed.
+    fn parse_conditional<'a>(
+        tokens: &mut impl Iterator<Item = Token<'a>>,
+        argument: &str,
+        position: usize,
+    ) -> Result<Conditional, TemplateError> {
+        let reference = Self::parse_reference(argument, position)?;
+
+        let (then_branch, closing) = Self::parse_block(tokens)?;
+        let else_branch = match closing {
+            Some(("endif", _)) => Vec::new(),
+            Some(("else", else_position)) => match Self::parse_block(tokens)? {
+                (else_branch, Some(("endif", _))) => else_branch,
+                (_, Some((keyword, position))) => {
+                    return Err(Self::syntax_error(position, &format!("unexpected [#{keyword}]")))
+                }
+                (_, None) => return Err(Self::syntax_error(else_position, "[#else] without [#endif]")),
+            },
+            _ => return Err(Self::syntax_error(position, "[#if] without [#endif]")),
+        };
+
+        Ok(Conditional {
+            reference,
+            then_branch,
+            else_branch,
+        })
+    }
+
+    /// Parses the body of a `[#block name]` section, the opening directive being already consumed.
+    fn parse_named_block<'a>(
+        tokens: &mut impl Iterator<Item = Token<'a>>,
+        argument: &str,
+        position: usize,
+    ) -> Result<Block, TemplateError> {
+        if argument.is_empty() || argument.contains(char::is_whitespace) {
+            return Err(Self::syntax_error(position, &format!("invalid block name '{argument}'")));
+        }
+
+        match Self::parse_block(tokens)? {
+            (body, Some(("endblock", _))) => Ok(Block {
+                name: argument.to_string(),
+                body,
+            }),
+            (_, Some((keyword, position))) => Err(Self::syntax_error(position, &format!("unexpected [#{keyword}]"))),
+            (_, None) => Err(Self::syntax_error(position, "[#block] without [#endblock]")),
+        }
+    }
+
+    /// Parses the body of a `[#for item in @reference]` loop, the opening directive being already consumed.
+    fn parse_loop<'a>(
+        tokens: &mut impl Iterator<Item = Token<'a>>,
+        argument: &str,
+        position: usize,
+    ) -> Result<Loop, TemplateError> {
+        let (head, separator) = match argument.split_once("separator=") {
+            Some((head, separator)) => (head, Self::parse_quoted(separator.trim(), position)?),
+            None => (argument, String::new()),
+        };
+
+        let (variable, reference) = match head.split_whitespace().collect::<Vec<&str>>()[..] {
+            [variable, "in", reference] => (variable, Self::parse_reference(reference, position)?),
+            _ => {
+                return Err(Self::syntax_error(
+                    position,
+                    &format!("expected 'item in @reference', got '{}'", head.trim()),
+                ))
+            }
+        };
+        let is_identifier = variable.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !is_identifier || variable == Loop::STATE {
+            return Err(Self::syntax_error(position, &format!("invalid loop variable '{variable}'")));
+        }
+
+        match Self::parse_block(tokens)? {
+            (body, Some(("endfor", _))) => Ok(Loop {
+                variable: variable.to_string(),
+                reference,
+                separator,
+                body,
+            }),
+            (_, Some((keyword, position))) => Err(Self::syntax_error(position, &format!("unexpected [#{keyword}]"))),
+            (_, None) => Err(Self::syntax_error(position, "[#for] without [#endfor]")),
+        }
+    }
+
+    /// Reads a `"quoted"` directive argument.
+    fn parse_quoted(argument: &str, position: usize) -> Result<String, TemplateError> {
+        argument
+            .strip_prefix('"')
+            .and_then(|argument| argument.strip_suffix('"'))
+            .map(str::to_string)
+            .ok_or_else(|| Self::syntax_error(position, &format!("expected a quoted value, got '{argument}'")))
+    }
+
+    /// Reads an `@reference` directive argument.
+    fn parse_reference(argument: &str, position: usize) -> Result<String, TemplateError> {
+        match argument.strip_prefix('@').map(str::trim) {
+            Some(reference) if !reference.is_empty() && !reference.contains(char::is_whitespace) => {
+                Ok(reference.to_string())
+            }
+            _ => Err(Self::syntax_error(
+                position,
+                &format!("expected an @reference, got '{argument}'"),
+            )),
+        }
+    }
+
+    /// Pushes the literal span `content[start..end]`, rejecting any unclosed placeholder or directive it contains.
+    fn push_literal<'a>(
+        tokens: &mut Vec<Token<'a>>,
+        content: &'a str,
+        start: usize,
+        end: usize,
+    ) -> Result<(), TemplateError> {
+        let literal = &content[start..end];
+        if let Some(offset) = literal.find("[@") {
+            return Err(Self::syntax_error(start + offset, "unclosed placeholder"));
+        }
+        if let Some(offset) = literal.find("[#") {
+            return Err(Self::syntax_error(start + offset, "unclosed directive"));
+        }
+        if let Some(offset) = literal.find("[>") {
+            return Err(Self::syntax_error(start + offset, "unclosed partial"));
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+        Ok(())
+    }
+
+    fn syntax_error(position: usize, reason: &str) -> TemplateError {
+        TemplateError::InvalidSyntax {
+            position,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_literal_and_placeholders() {
+        let nodes = TemplateParser::parse("Prize: [@prize] ([@ref_link]).").unwrap();
+
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(nodes[0], TemplateNode::Literal("Prize: ".to_string()));
+        assert!(matches!(&nodes[1], TemplateNode::Placeholder(p) if p.get_reference() == "prize"));
+        assert_eq!(nodes[2], TemplateNode::Literal(" (".to_string()));
+        assert!(matches!(&nodes[3], TemplateNode::Placeholder(p) if p.get_source() == "[@ref_link]"));
+        assert_eq!(nodes[4], TemplateNode::Literal(").".to_string()));
+    }
+
+    #[test]
+    fn test_parse_keeps_whitespace() {
+        let content = "Line one\n\n    indented  [@a]\t\n";
+        let nodes = TemplateParser::parse(content).unwrap();
+
+        assert_eq!(nodes[0], TemplateNode::Literal("Line one\n\n    indented  ".to_string()));
+        assert_eq!(nodes[2], TemplateNode::Literal("\t\n".to_string()));
+    }
+
+    #[test]
+    fn test_parse_rejects_unclosed_placeholder() {
+        let result = TemplateParser::parse("Hello [@name and [@other]");
+
+        assert_eq!(
+            result,
+            Err(TemplateError::InvalidSyntax {
+                position: 6,
+                reason: "unclosed placeholder".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_placeholder() {
+        let result = TemplateParser::parse("Hello [@ ]");
+
+        assert!(matches!(result, Err(TemplateError::InvalidSyntax { position: 6, .. })));
+    }
+
+    #[test]
+    fn test_parse_placeholder_filters() {
+        let nodes = TemplateParser::parse("[@name | upper|truncate:10 ]").unwrap();
+
+        match &nodes[0] {
+            TemplateNode::Placeholder(placeholder) => {
+                assert_eq!(placeholder.get_reference(), "name");
+                assert_eq!(placeholder.get_filters(), &[Filter::Upper, Filter::Truncate(10)]);
+            }
+            other => panic!("expected a placeholder, got {other:?}"),
+        }
+        assert!(matches!(
+            TemplateParser::parse("Hi [@name|shout]"),
+            Err(TemplateError::InvalidSyntax { position: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_conditional_with_else() {
+        let nodes = TemplateParser::parse("[#if @prize]Prize: [@prize][#else]No prize[#endif]!").unwrap();
+
+        assert_eq!(nodes.len(), 2);
+        match &nodes[0] {
+            TemplateNode::Conditional(conditional) => {
+                assert_eq!(conditional.get_reference(), "prize");
+                assert_eq!(conditional.get_then_branch().len(), 2);
+                assert_eq!(conditional.get_else_branch(), &[TemplateNode::Literal("No prize".to_string())]);
+            }
+            other => panic!("expected a conditional, got {other:?}"),
+        }
+        assert_eq!(
+            TemplateParser::collect_references(&nodes),
+            HashSet::from(["prize"])
+        );
+    }
+
+    #[test]
+    fn test_parse_nested_conditionals() {
+        let nodes = TemplateParser::parse("[#if @a][#if @b]both[#endif][#endif]").unwrap();
+
+        assert_eq!(
+            TemplateParser::collect_references(&nodes),
+            HashSet::from(["a", "b"])
+        );
+    }
+
+    #[test]
+    fn test_parse_includes() {
+        let nodes = TemplateParser::parse("[#if @a][> header ][#endif]Body[>footer]").unwrap();
+
+        assert_eq!(nodes[1], TemplateNode::Literal("Body".to_string()));
+        assert_eq!(nodes[2], TemplateNode::Include("footer".to_string()));
+        assert_eq!(TemplateParser::collect_includes(&nodes), vec!["header", "footer"]);
+        assert!(matches!(
+            TemplateParser::parse("Body [>foot er]"),
+            Err(TemplateError::InvalidSyntax { position: 5, .. })
+        ));
+        assert!(matches!(
+            TemplateParser::parse("Body [>footer"),
+            Err(TemplateError::InvalidSyntax { position: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_named_blocks() {
+        let nodes = TemplateParser::parse("<h1>[#block title]Default [@name][#endblock]</h1>[#block body][#endblock]").unwrap();
+        let blocks = TemplateParser::collect_blocks(&nodes);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks["title"].len(), 2);
+        assert!(blocks["body"].is_empty());
+        assert!(matches!(
+            TemplateParser::parse("[#block a]x[#endblock][#block a]y[#endblock]"),
+            Err(TemplateError::InvalidSyntax { position: 22, .. })
+        ));
+        assert!(matches!(
+            TemplateParser::parse("[#block a]x"),
+            Err(TemplateError::InvalidSyntax { position: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_override_blocks() {
+        let layout = TemplateParser::parse("<[#block title]Default[#endblock]|[#if @a][#block body]-[#endblock][#endif]>").unwrap();
+        let child = TemplateParser::parse("[#block title]Mine[#endblock]").unwrap();
+
+        let resolved = TemplateParser::override_blocks(&layout, &TemplateParser::collect_blocks(&child));
+        let blocks = TemplateParser::collect_blocks(&resolved);
+
+        assert_eq!(blocks["title"], &[TemplateNode::Literal("Mine".to_string())]);
+        assert_eq!(blocks["body"], &[TemplateNode::Literal("-".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_rejects_unbalanced_blocks() {
+        assert!(matches!(
+            TemplateParser::parse("[#if @a]missing end"),
+            Err(TemplateError::InvalidSyntax { position: 0, .. })
+        ));
+        assert!(matches!(
+            TemplateParser::parse("stray [#endif]"),
+            Err(TemplateError::InvalidSyntax { position: 6, .. })
+        ));
+        assert!(matches!(
+            TemplateParser::parse("[#if prize]x[#endif]"),
+            Err(TemplateError::InvalidSyntax { position: 0, .. })
+        ));
+        assert!(matches!(
+            TemplateParser::parse("[#unless @a]x[#endif]"),
+            Err(TemplateError::InvalidSyntax { position: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_loops() {
+        let nodes = TemplateParser::parse("[#for speaker in @speakers separator=\", \"][@loop.index]. [@speaker][#endfor]").unwrap();
+
+        let TemplateNode::Loop(body) = &nodes[0] else { panic!("expected a loop") };
+        assert_eq!(body.get_variable(), "speaker");
+        assert_eq!(body.get_reference(), "speakers");
+        assert_eq!(body.get_separator(), ", ");
+        assert_eq!(body.get_body().len(), 3);
+        assert_eq!(TemplateParser::collect_references(&nodes), HashSet::from(["speakers"]));
+
+        assert!(TemplateParser::parse("[#for speaker in @speakers]").is_err());
+        assert!(TemplateParser::parse("[#for speaker of @speakers][#endfor]").is_err());
+        assert!(TemplateParser::parse("[#for loop in @speakers][#endfor]").is_err());
+        assert!(TemplateParser::parse("[#for s in @speakers separator=, ][#endfor]").is_err());
+        assert!(TemplateParser::parse("[#for s in @speakers][#endif]").is_err());
+    }
+
+    #[test]
+    fn test_collect_references_in_nested_loops() {
+        let content = "[#for row in @rows][#for cell in @row][@cell][@title][#endfor][#if @loop.last].[#endif][#endfor]";
+        let nodes = TemplateParser::parse(content).unwrap();
+
+        assert_eq!(TemplateParser::collect_references(&nodes), HashSet::from(["rows", "title"]));
+    }
+}
@@ -0,0 +1,205 @@
+//! # TemplateRenderer
+//!
+//! The `TemplateRenderer` walks the `TemplateNode`s of a template and streams the output for one `InstanceObject`
+//! into any `std::io::Write` sink.
+//!
+//! ## Responsibilities:
+//! - Copies literal spans untouched.
+//! - Runs placeholder values through their filters and applies the `NullPolicy` of the `RenderContext`.
+//! - Escapes substituted values with the `EscapeMode` of the template or of the placeholder.
+//! - Selects the branch of conditional blocks and renders the body of named blocks.
+//! - Follows dotted references through nested objects and through the instances referenced by `Reference` entities.
+//! - Repeats loop bodies once per element, binding the element and the loop state for the body.
+//! - Renders included partials with their own fallback values, and with the escape mode of the including template,
+//!   or their own one when the including template writes raw values.
+
+use std::{collections::BTreeMap, io::Write};
+
+use crate::core::{errors::TemplateError, instance::{instance_entities::InstanceAttributes, instance_object::InstanceObject}, model::entity::EntityTraits};
+
+use super::{template::Template, template_context::{NullPolicy, RenderContext}, template_escape::EscapeMode, template_parser::{Loop, Placeholder, TemplateNode}};
+
+/// Renders template nodes for a single `InstanceObject`.
+pub(crate) struct TemplateRenderer<'a> {
+    object: &'a InstanceObject,
+    context: &'a RenderContext<'a>,
+    root: &'a Template,
+    template: &'a Template,
+    escape_mode: EscapeMode,
+    locals: Vec<(String, InstanceAttributes)>,
+    null_entities: Vec<String>,
+}
+
+impl<'a> TemplateRenderer<'a> {
+    /// Creates a renderer of the template for the given instance and context.
+    pub(crate) fn new(object: &'a InstanceObject, context: &'a RenderContext<'a>, template: &'a Template) -> Self {
+        TemplateRenderer {
+            object,
+            context,
+            root: template,
+            template,
+            escape_mode: template.get_escape_mode(),
+            locals: Vec::new(),
+            null_entities: Vec::new(),
+        }
+    }
+
+    /// Renders the nodes into the writer, span by span.
+    ///
+    /// Rendering goes on after a rejected missing value so that all of them are reported,
+    /// the writer then holding a partial output.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateError::NullEntities` when the `NullPolicy` rejects missing values,
+    /// `TemplateError::IoError` when the writer fails, or the error of a failing filter.
+    pub(crate) fn render<W: Write>(mut self, nodes: &[TemplateNode], output: &mut W) -> Result<(), TemplateError> {
+        self.render_nodes(nodes, output)?;
+
+        match self.null_entities.is_empty() {
+            true => Ok(()),
+            false => {
+                self.null_entities.sort();
+                self.null_entities.dedup();
+                Err(TemplateError::NullEntities(self.null_entities))
+            }
+        }
+    }
+
+    fn render_nodes<W: Write>(&mut self, nodes: &[TemplateNode], output: &mut W) -> Result<(), TemplateError> {
+        nodes.iter().try_for_each(|node| match node {
+            TemplateNode::Literal(text) => Self::write(output, text),
+            TemplateNode::Placeholder(placeholder) => self.render_placeholder(placeholder, output),
+            TemplateNode::Conditional(conditional) => {
+                let holds = self
+                    .lookup(conditional.get_reference())
+                    .is_some_and(InstanceAttributes::is_truthy);
+                let branch = match holds {
+                    true => conditional.get_then_branch(),
+                    false => conditional.get_else_branch(),
+                };
+                self.render_nodes(branch, output)
+            }
+            TemplateNode::Block(block) => self.render_nodes(block.get_body(), output),
+            TemplateNode::Loop(body) => self.render_loop(body, output),
+            TemplateNode::Include(key) => {
+                let partial = self
+                    .root
+                    .get_partial(key)
+                    .ok_or_else(|| TemplateError::MissingPartial(key.to_string()))?;
+
+                let escape_mode = match self.escape_mode {
+                    EscapeMode::Raw => partial.get_escape_mode(),
+                    escape_mode => escape_mode,
+                };
+                let including = std::mem::replace(&mut self.template, partial);
+                let including_mode = std::mem::replace(&mut self.escape_mode, escape_mode);
+                let rendered = self.render_nodes(partial.get_plan(), output);
+                self.template = including;
+                self.escape_mode = including_mode;
+                rendered
+            }
+        })
+    }
+
+    /// Renders the body of a loop for each element, a missing collection rendering nothing.
+    fn render_loop<W: Write>(&mut self, body: &Loop, output: &mut W) -> Result<(), TemplateError> {
+        let items = self
+            .lookup(body.get_reference())
+            .and_then(InstanceAttributes::get_items)
+            .map(<[InstanceAttributes]>::to_vec)
+            .unwrap_or_default();
+
+        let last = items.len().saturating_sub(1);
+        items.into_iter().enumerate().try_for_each(|(index, item)| {
+            if index > 0 {
+                Self::write(output, body.get_separator())?;
+            }
+            let state = BTreeMap::from([
+                ("index".to_string(), InstanceAttributes::U64(Some(index as u64 + 1))),
+                ("first".to_string(), InstanceAttributes::Bool(Some(index == 0))),
+                ("last".to_string(), InstanceAttributes::Bool(Some(index == last))),
+            ]);
+            let bindings = [
+                (body.get_variable().to_string(), item),
+                (Loop::STATE.to_string(), InstanceAttributes::Object(state)),
+            ];
+            let depth = self.locals.len();
+            self.locals.extend(bindings);
+            let rendered = self.render_nodes(body.get_body(), output);
+            self.locals.truncate(depth);
+            rendered
+        })
+    }
+
+    /// Returns the value of a reference, looking into the innermost loop bindings first, then into the instance,
+    /// and following its dotted path through nested objects and referenced instances.
+    fn lookup(&self, reference: &str) -> Option<&InstanceAttributes> {
+        let (root, path) = match reference.split_once('.') {
+            Some((root, path)) => (root, Some(path)),
+            None => (reference, None),
+        };
+
+        let value = self
+            .locals
+            .iter()
+            .rev()
+            .find_map(|(name, value)| (name == root).then_some(value))
+            .or_else(|| self.object.entities.get(root).map(|entity| entity.get_attribute()))?;
+
+        path.map_or(Some(value), |path| {
+            path.split('.').try_fold(value, |value, field| match value {
+                InstanceAttributes::Reference(Some(id)) => self
+                    .context
+                    .get_instance_objects()?
+                    .get(id)
+                    .ok()?
+                    .entities
+                    .get(field)
+                    .map(|entity| entity.get_attribute()),
+                value => value.get_path(field),
+            })
+        })
+    }
+
+    /// Writes the filtered and escaped value of a placeholder, an entity missing from the instance counting as a missing value.
+    fn render_placeholder<W: Write>(&mut self, placeholder: &Placeholder, output: &mut W) -> Result<(), TemplateError> {
+        let reference = placeholder.get_reference();
+        let value = self
+            .lookup(reference)
+            .cloned()
+            .unwrap_or(InstanceAttributes::Text(None));
+
+        let value = placeholder
+            .get_filters()
+            .iter()
+            .try_fold(value, |value, filter| filter.apply(value))?;
+
+        let escape_mode = placeholder
+            .get_filters()
+            .iter()
+            .rev()
+            .find_map(|filter| filter.get_escape_mode())
+            .unwrap_or(self.escape_mode);
+
+        if !value.is_null() {
+            return Self::write(output, &escape_mode.escape(&value.to_string()));
+        }
+
+        match (self.context.get_null_policy(), self.template.get_fallback(reference)) {
+            (NullPolicy::Empty, _) => Ok(()),
+            (NullPolicy::KeepPlaceholder, _) => Self::write(output, placeholder.get_source()),
+            (NullPolicy::Fallback, Some(fallback)) => Self::write(output, &escape_mode.escape(fallback)),
+            (NullPolicy::Error, _) | (NullPolicy::Fallback, None) => {
+                self.null_entities.push(reference.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    fn write<W: Write>(output: &mut W, text: &str) -> Result<(), TemplateError> {
+        output
+            .write_all(text.as_bytes())
+            .map_err(TemplateError::from)
+    }
+}
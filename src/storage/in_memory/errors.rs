@@ -3,5 +3,6 @@
 pub enum CollectionError {
     MissingObject(String),
     NoMatchingObject(String),
-    NoParentObject(String)
+    NoParentObject(String),
+    DuplicateName(String)
 }
\ No newline at end of file
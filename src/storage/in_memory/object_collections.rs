@@ -1,30 +1,31 @@
 use std::collections::HashMap;
 
-use crate::core::{instance::instance_entities::{InstanceAttributes, InstanceEntity}, meta::meta_entity::{MetaAttributes, MetaEntity}, model::{entity::EntityTraits, Object, UniqueId}};
+use crate::core::{instance::instance_entities::{InstanceAttributes, InstanceEntity}, meta::{meta_entity::{MetaAttributes, MetaEntity}, meta_object::MetaExtension}, model::{entity::EntityTraits, Object, UniqueId}};
 
 use super::errors::CollectionError;
 
 use crate::core::model::unique_id::Identifier;
 
 
-pub struct ObjectCollection<E: EntityTraits<A>,A>{
-    data:HashMap<UniqueId, Object<E,A>>
+#[derive(Debug)]
+pub struct ObjectCollection<E: EntityTraits<A>,A,X = ()>{
+    data:HashMap<UniqueId, Object<E,A,X>>
 }
 
-impl <E:EntityTraits<A>+Clone,A:Clone> ObjectCollection<E,A>{
-    pub fn new()->ObjectCollection<E,A>{
+impl <E:EntityTraits<A>+Clone,A:Clone,X:Clone+Default> ObjectCollection<E,A,X>{
+    pub fn new()->ObjectCollection<E,A,X>{
         ObjectCollection { data: HashMap::new() }
     }
 
    
-    pub fn get(&self, id:&UniqueId)->Result<&Object<E,A>,CollectionError> {
+    pub fn get(&self, id:&UniqueId)->Result<&Object<E,A,X>,CollectionError> {
         self.data
         .get(&id.clone())
         .ok_or_else(|| CollectionError::MissingObject(id.get_id().to_string()))
     }
 
     
-    pub fn insert(&mut self, value:&Object<E,A>) {
+    pub fn insert(&mut self, value:&Object<E,A,X>) {
         self.data.insert(value.get_id().clone(), value.clone());
     }
 
@@ -36,7 +37,12 @@ impl <E:EntityTraits<A>+Clone,A:Clone> ObjectCollection<E,A>{
         .and_then(|_| Ok(()))
     }
 
-    pub fn get_objects_by_meta_id(&self, meta_id:&UniqueId)->Result<Vec<&Object<E, A>>, CollectionError> {
+    /// Returns an iterator over all the objects of the collection, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Object<E, A, X>> {
+        self.data.values()
+    }
+
+    pub fn get_objects_by_meta_id(&self, meta_id:&UniqueId)->Result<Vec<&Object<E, A, X>>, CollectionError> {
         let output=self
         .data
         .iter()
@@ -51,7 +57,7 @@ impl <E:EntityTraits<A>+Clone,A:Clone> ObjectCollection<E,A>{
                 None
             }
         })
-        .collect::<Vec<&Object<E, A>>>();
+        .collect::<Vec<&Object<E, A, X>>>();
     
         if output.is_empty(){
             Err(CollectionError::NoMatchingObject(meta_id.get_id().to_string()))
@@ -61,12 +67,12 @@ impl <E:EntityTraits<A>+Clone,A:Clone> ObjectCollection<E,A>{
         
     }
 
-    pub fn get_objects_by_name(&self, name:&str)->Result<Vec<&Object<E, A>>, CollectionError> {
+    pub fn get_objects_by_name(&self, name:&str)->Result<Vec<&Object<E, A, X>>, CollectionError> {
         let output=self
         .data
         .iter()
         .filter_map(|(_,v)|if v.get_name()==name {Some(v)} else {None})
-        .collect::<Vec<&Object<E, A>>>();
+        .collect::<Vec<&Object<E, A, X>>>();
     
         if output.is_empty(){
             Err(CollectionError::NoMatchingObject(name.to_string()))
@@ -78,7 +84,7 @@ impl <E:EntityTraits<A>+Clone,A:Clone> ObjectCollection<E,A>{
 
 }
 
-pub type MetaObjectCollection=ObjectCollection<MetaEntity,MetaAttributes>;
+pub type MetaObjectCollection=ObjectCollection<MetaEntity,MetaAttributes,MetaExtension>;
 pub type InstanceObjectCollection=ObjectCollection<InstanceEntity,InstanceAttributes>;
 
 
@@ -88,13 +94,12 @@ mod tests {
     use crate::core::instance::instance_object::InstanceObject;
     use crate::core::meta::meta_object::MetaObject;
     use crate::core::model::unique_id::Identifier;
-    use crate::core::meta::meta_entity::MetaAttributes;
 
     
 
     #[test]
     fn test_insert_and_get_object() {
-        let mut collection = ObjectCollection::<MetaEntity, MetaAttributes>::new();
+        let mut collection = MetaObjectCollection::new();
 
         let obj = MetaObject::new_meta("TestObject").unwrap();
 
@@ -110,7 +115,7 @@ mod tests {
 
     #[test]
     fn test_remove_object() {
-        let mut collection = ObjectCollection::<MetaEntity, MetaAttributes>::new();
+        let mut collection = MetaObjectCollection::new();
 
         let obj = MetaObject::new_meta("TestObject").unwrap();
 
@@ -150,7 +155,7 @@ mod tests {
 
     #[test]
     fn test_get_objects_by_name() {
-        let mut collection = ObjectCollection::<MetaEntity, MetaAttributes>::new();
+        let mut collection = MetaObjectCollection::new();
 
         let obj = MetaObject::new_meta("TestObject").unwrap();
 
@@ -167,7 +172,7 @@ mod tests {
 
     #[test]
     fn test_no_matching_object() {
-        let collection = ObjectCollection::<MetaEntity, MetaAttributes>::new();
+        let collection = MetaObjectCollection::new();
 
         // Search for a non-existent object
         let result = collection.get_objects_by_name("NonExistentObject");
@@ -6,41 +6,66 @@ use super::errors::CollectionError;
 use crate::core::model::unique_id::Identifier;
 
 
+#[derive(Debug, Default)]
 pub struct TemplateCollection<Template>{
-    data:HashMap<UniqueId, Template>
+    data:HashMap<UniqueId, Template>,
+    names:HashMap<String, UniqueId>,
 }
 
 impl TemplateCollection<Template>{
 
-    fn new()->TemplateCollection<Template>{
-        TemplateCollection { data: HashMap::new() }
+    pub fn new()->TemplateCollection<Template>{
+        TemplateCollection { data: HashMap::new(), names: HashMap::new() }
     }
 
-   
-    fn get(&self, id:&UniqueId)->Result<&Template,CollectionError> {
+
+    pub fn get(&self, id:&UniqueId)->Result<&Template,CollectionError> {
         self.data
-        .get(&id.clone())
+        .get(id)
         .ok_or_else(|| CollectionError::MissingObject(id.get_id().to_string()))
     }
 
-    
-    fn insert(&mut self, value:&Template) {
-        self.data.insert(value.get_id().clone(), value.clone());
+
+    /// Inserts a template, replacing the one with the same id.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CollectionError::DuplicateName` if another template already has the same name,
+    /// so that `[>name]` partials always resolve to a single template.
+    pub fn insert(&mut self, value:&Template)->Result<(),CollectionError> {
+        if let Some(name) = value.get_name() {
+            match self.names.get(name) {
+                Some(id) if id != value.get_id() => return Err(CollectionError::DuplicateName(name.to_string())),
+                _ => {}
+            }
+        }
+
+        if let Some(name) = self.data.insert(value.get_id().clone(), value.clone()).as_ref().and_then(Template::get_name) {
+            self.names.remove(name);
+        }
+        if let Some(name) = value.get_name() {
+            self.names.insert(name.to_string(), value.get_id().clone());
+        }
+        Ok(())
     }
 
-   
-    fn remove(&mut self, id:&UniqueId)->Result<(),CollectionError> {
+
+    pub fn remove(&mut self, id:&UniqueId)->Result<(),CollectionError> {
         self.data
-        .remove(&id.get_id())
+        .remove(id.get_id())
         .ok_or_else(|| CollectionError::MissingObject(id.to_string()))
-        .and_then(|_| Ok(()))
+        .map(|template| {
+            if let Some(name) = template.get_name() {
+                self.names.remove(name);
+            }
+        })
     }
 
-    fn get_by_parent_object(&self, parent_object_id:&UniqueId)->Result<Vec<&Template>, CollectionError>{
+    pub fn get_by_parent_object(&self, parent_object_id:&UniqueId)->Result<Vec<&Template>, CollectionError>{
         let ouput = self
         .data
-        .iter()
-        .filter_map(|(_, template)|if template.get_parent_object()==parent_object_id {Some(template)} else {None} )
+        .values()
+        .filter(|template| template.get_parent_object()==parent_object_id)
         .collect::<Vec<&Template>>();
 
         if ouput.is_empty(){
@@ -49,11 +74,26 @@ impl TemplateCollection<Template>{
             Ok(ouput)
         }
     }
+
+    pub fn get_by_name(&self, name:&str)->Result<&Template, CollectionError>{
+        self.names
+        .get(name)
+        .and_then(|id| self.data.get(id))
+        .ok_or_else(|| CollectionError::NoMatchingObject(name.to_string()))
+    }
+
+    /// Looks a template up by its id, then by its name, as done for `[>key]` partials.
+    pub fn resolve(&self, key:&str)->Result<&Template, CollectionError>{
+        key.parse::<UniqueId>()
+        .ok()
+        .and_then(|id| self.data.get(&id))
+        .map_or_else(|| self.get_by_name(key), Ok)
+    }
 }
 
 #[cfg(test)]
 mod tests{
-    use crate::core::{meta::meta_object::MetaObject, template::{self, template_builder::TemplateBuilder}};
+    use crate::core::{meta::meta_object::MetaObject, template::template_builder::TemplateBuilder};
 
     use super::*;
 
@@ -69,11 +109,44 @@ mod tests{
 
         let template_id=template.get_id();
 
-        template_collection.insert(&template);
-        let retrieved_template = template_collection.get(&template_id);
+        template_collection.insert(&template).unwrap();
+        let retrieved_template = template_collection.get(template_id);
 
         assert!(retrieved_template.is_ok());
         assert_eq!(retrieved_template.unwrap().get_id(), template_id);
- 
+
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_resolve_by_id_and_name() {
+        let mut template_collection=TemplateCollection::new();
+
+        let meta_obj = MetaObject::new_meta("TestObject").unwrap();
+
+        let template=TemplateBuilder::instanciate("Footer", &meta_obj).with_name("footer").build().unwrap();
+
+        template_collection.insert(&template).unwrap();
+
+        assert_eq!(template_collection.resolve("footer").unwrap().get_id(), template.get_id());
+        assert_eq!(template_collection.resolve(&template.get_id().to_string()).unwrap().get_id(), template.get_id());
+        assert!(template_collection.resolve("header").is_err());
+    }
+
+    #[test]
+    fn test_insert_duplicate_name() {
+        let mut template_collection=TemplateCollection::new();
+
+        let meta_obj = MetaObject::new_meta("TestObject").unwrap();
+
+        let footer=TemplateBuilder::instanciate("Footer", &meta_obj).with_name("footer").build().unwrap();
+        let other=TemplateBuilder::instanciate("Other footer", &meta_obj).with_name("footer").build().unwrap();
+
+        template_collection.insert(&footer).unwrap();
+        template_collection.insert(&footer).unwrap();
+        assert!(matches!(template_collection.insert(&other), Err(CollectionError::DuplicateName(name)) if name == "footer"));
+
+        template_collection.remove(footer.get_id()).unwrap();
+        template_collection.insert(&other).unwrap();
+        assert_eq!(template_collection.resolve("footer").unwrap().get_id(), other.get_id());
+    }
+}